@@ -0,0 +1,68 @@
+//! Color-buffer outlines on a 2D camera.
+//!
+//! `EdgeDetection2dPlugin` runs the edge pass in the 2D pipeline. 2D views have
+//! no depth/normal prepass, so only color edges are detected; the depth and
+//! normal settings on `EdgeDetection` are ignored.
+
+use bevy::prelude::*;
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetection2dPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetection2dPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+#[derive(Component)]
+struct Shape;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let shapes = [
+        meshes.add(Circle::new(50.0)),
+        meshes.add(Rectangle::new(100.0, 70.0)),
+        meshes.add(RegularPolygon::new(55.0, 6)),
+        meshes.add(Triangle2d::new(
+            Vec2::new(0.0, 55.0),
+            Vec2::new(-50.0, -40.0),
+            Vec2::new(50.0, -40.0),
+        )),
+    ];
+    let colors = [
+        Color::srgb(0.9, 0.4, 0.3),
+        Color::srgb(0.3, 0.7, 0.9),
+        Color::srgb(0.9, 0.8, 0.3),
+        Color::srgb(0.5, 0.9, 0.4),
+    ];
+
+    for (i, (shape, color)) in shapes.into_iter().zip(colors).enumerate() {
+        commands.spawn((
+            Mesh2d(shape),
+            MeshMaterial2d(materials.add(color)),
+            Transform::from_xyz(-240.0 + i as f32 * 160.0, 0.0, 0.0),
+            Shape,
+        ));
+    }
+
+    commands.spawn((
+        Camera2d,
+        Msaa::Off,
+        EdgeDetection {
+            color_threshold: 0.15,
+            color_thickness: 2.0,
+            ..EdgeDetection::color_only()
+        },
+    ));
+}
+
+fn rotate(mut query: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
+    for mut transform in &mut query {
+        transform.rotate_z(time.delta_secs() / 2.);
+    }
+}
@@ -9,7 +9,9 @@ use bevy::{
     prelude::*,
     render::render_resource::{Extent3d, TextureDimension, TextureFormat},
 };
-use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin, EdgeOperator};
+use bevy_edge_detection_outline::{
+    DebugView, EdgeColorAnim, EdgeDetection, EdgeDetectionPlugin, EdgeKernel, EdgeOperator,
+};
 use bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui};
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 
@@ -18,7 +20,7 @@ fn main() {
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
         // Edge detection runs after tonemapping and before the anti-aliasing
         // passes (FXAA/SMAA), so anti-aliasing smooths the detected outlines.
-        .add_plugins(EdgeDetectionPlugin)
+        .add_plugins(EdgeDetectionPlugin::default())
         .add_plugins(EguiPlugin::default())
         .add_plugins(PanOrbitCameraPlugin)
         .add_systems(Startup, (setup, spawn_text))
@@ -187,6 +189,12 @@ fn uv_debug_texture() -> Image {
     )
 }
 
+/// A slider over the field's [`EdgeDetection::FIELD_RANGES`] slider range.
+fn field_slider<'a>(value: &'a mut f32, name: &'static str) -> egui::Slider<'a> {
+    let range = EdgeDetection::field_range(name).expect("field without a range");
+    egui::Slider::new(value, range.slider_min..=range.slider_max).text(name)
+}
+
 fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut EdgeDetection>) {
     let Ok(ctx) = ctx.ctx_mut() else {
         return;
@@ -198,10 +206,10 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                     &mut edge_detection.enable_depth,
                     "enable_depth",
                 ));
-                ui.add(
-                    egui::Slider::new(&mut edge_detection.depth_threshold, 0.0..=8.0)
-                        .text("depth_threshold"),
-                );
+                ui.add(field_slider(
+                    &mut edge_detection.depth_threshold,
+                    "depth_threshold",
+                ));
             });
 
             ui.horizontal(|ui| {
@@ -209,10 +217,22 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                     &mut edge_detection.enable_normal,
                     "enable_normal",
                 ));
-                ui.add(
-                    egui::Slider::new(&mut edge_detection.normal_threshold, 0.0..=8.0)
-                        .text("normal_threshold"),
-                );
+                ui.add(field_slider(
+                    &mut edge_detection.normal_threshold,
+                    "normal_threshold",
+                ));
+                ui.add(field_slider(
+                    &mut edge_detection.rim_strength,
+                    "rim_strength",
+                ));
+                ui.add(field_slider(
+                    &mut edge_detection.edge_smoothness,
+                    "edge_smoothness",
+                ));
+                ui.add(field_slider(
+                    &mut edge_detection.thickness_depth_falloff,
+                    "thickness_depth_falloff",
+                ));
             });
 
             ui.horizontal(|ui| {
@@ -220,32 +240,64 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                     &mut edge_detection.enable_color,
                     "enable_color",
                 ));
-                ui.add(
-                    egui::Slider::new(&mut edge_detection.color_threshold, 0.0..=8.0)
-                        .text("color_threshold"),
-                );
+                ui.add(field_slider(
+                    &mut edge_detection.color_threshold,
+                    "color_threshold",
+                ));
             });
 
+            ui.add(field_slider(
+                &mut edge_detection.depth_thickness,
+                "depth_thickness",
+            ));
+            ui.add(field_slider(
+                &mut edge_detection.normal_thickness,
+                "normal_thickness",
+            ));
+            ui.add(field_slider(
+                &mut edge_detection.color_thickness,
+                "color_thickness",
+            ));
+
+            let mut use_far = edge_detection.depth_threshold_far.is_some();
+            ui.checkbox(&mut use_far, "depth threshold ramp");
+            if use_far {
+                let near = edge_detection.depth_threshold;
+                let far = edge_detection.depth_threshold_far.get_or_insert(near);
+                ui.add(field_slider(far, "depth_threshold_far"));
+                ui.add(
+                    egui::Slider::new(&mut edge_detection.depth_threshold_ramp.x, 0.0..=100.0)
+                        .text("ramp start"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut edge_detection.depth_threshold_ramp.y, 0.0..=200.0)
+                        .text("ramp end"),
+                );
+            } else {
+                edge_detection.depth_threshold_far = None;
+            }
+
+            let mut use_crease = edge_detection.crease_threshold.is_some();
+            ui.checkbox(&mut use_crease, "depth creases");
+            if use_crease {
+                let crease = edge_detection.crease_threshold.get_or_insert(1.0);
+                ui.add(field_slider(crease, "crease_threshold"));
+            } else {
+                edge_detection.crease_threshold = None;
+            }
+
+            ui.add(field_slider(
+                &mut edge_detection.steep_angle_threshold,
+                "steep_angle_threshold",
+            ));
+            ui.add(field_slider(
+                &mut edge_detection.steep_angle_multiplier,
+                "steep_angle_multiplier",
+            ));
+            ui.add(field_slider(&mut edge_detection.depth_bias, "depth_bias"));
             ui.add(
-                egui::Slider::new(&mut edge_detection.depth_thickness, 0.0..=8.0)
-                    .text("depth_thickness"),
-            );
-            ui.add(
-                egui::Slider::new(&mut edge_detection.normal_thickness, 0.0..=8.0)
-                    .text("normal_thickness"),
-            );
-            ui.add(
-                egui::Slider::new(&mut edge_detection.color_thickness, 0.0..=8.0)
-                    .text("color_thickness"),
-            );
-
-            ui.add(
-                egui::Slider::new(&mut edge_detection.steep_angle_threshold, 0.0..=1.0)
-                    .text("steep_angle_threshold"),
-            );
-            ui.add(
-                egui::Slider::new(&mut edge_detection.steep_angle_multiplier, 0.0..=1.0)
-                    .text("steep_angle_multiplier"),
+                egui::Slider::new(&mut edge_detection.depth_prefilter, 0..=4)
+                    .text("depth_prefilter"),
             );
 
             ui.horizontal(|ui| {
@@ -274,6 +326,19 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                 ui.label("uv_distortion_strength");
             });
 
+            ui.add(field_slider(
+                &mut edge_detection.distortion_rotation,
+                "distortion_rotation",
+            ));
+            ui.add(
+                egui::Slider::new(&mut edge_detection.uv_distortion_speed.x, 0.0..=1.0)
+                    .text("uv_distortion_speed.x"),
+            );
+            ui.add(
+                egui::Slider::new(&mut edge_detection.uv_distortion_speed.y, 0.0..=1.0)
+                    .text("uv_distortion_speed.y"),
+            );
+
             // Operator selection
             ui.separator();
             ui.horizontal(|ui| {
@@ -287,7 +352,31 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                 if ui.selectable_label(edge_detection.operator == EdgeOperator::PixelArt, "PixelArt").clicked() {
                     edge_detection.operator = EdgeOperator::PixelArt;
                 }
+                if ui.selectable_label(edge_detection.operator == EdgeOperator::Roberts, "Roberts (gather)").clicked() {
+                    edge_detection.operator = EdgeOperator::Roberts;
+                }
             });
+            if edge_detection.operator == EdgeOperator::Sobel {
+                ui.horizontal(|ui| {
+                    ui.label("kernel:");
+                    for kernel in [
+                        EdgeKernel::Sobel,
+                        EdgeKernel::Scharr,
+                        EdgeKernel::Prewitt,
+                        EdgeKernel::Laplacian,
+                    ] {
+                        if ui
+                            .selectable_label(
+                                edge_detection.kernel == kernel,
+                                format!("{kernel:?}"),
+                            )
+                            .clicked()
+                        {
+                            edge_detection.kernel = kernel;
+                        }
+                    }
+                });
+            }
 
             ui.separator();
             let mut color = edge_detection.edge_color.to_srgba().to_f32_array_no_alpha();
@@ -328,7 +417,97 @@ fn edge_detection_ui(mut ctx: EguiContexts, mut edge_detection: Single<&mut Edge
                 ui.label("crease_color");
             });
 
+            // Color-buffer edge color
+            let mut use_col = edge_detection.color_edge_color.is_some();
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut use_col, "");
+                if use_col {
+                    let col = edge_detection.color_edge_color.get_or_insert(fallback);
+                    let mut cc = col.to_srgba().to_f32_array_no_alpha();
+                    egui::color_picker::color_edit_button_rgb(ui, &mut cc);
+                    *col = Color::srgb_from_array(cc);
+                } else {
+                    edge_detection.color_edge_color = None;
+                }
+                ui.label("color_edge_color");
+            });
+
+            // Edge color animation
+            ui.horizontal(|ui| {
+                ui.label("edge_color_animation:");
+                let anim = edge_detection.edge_color_animation;
+                if ui
+                    .selectable_label(anim == EdgeColorAnim::None, "None")
+                    .clicked()
+                {
+                    edge_detection.edge_color_animation = EdgeColorAnim::None;
+                }
+                if ui
+                    .selectable_label(matches!(anim, EdgeColorAnim::Pulse { .. }), "Pulse")
+                    .clicked()
+                {
+                    edge_detection.edge_color_animation = EdgeColorAnim::Pulse {
+                        speed: 1.0,
+                        alt_color: Color::WHITE,
+                    };
+                }
+                if ui
+                    .selectable_label(matches!(anim, EdgeColorAnim::Hue { .. }), "Hue")
+                    .clicked()
+                {
+                    edge_detection.edge_color_animation = EdgeColorAnim::Hue { speed: 0.25 };
+                }
+            });
+            match &mut edge_detection.edge_color_animation {
+                EdgeColorAnim::None => {}
+                EdgeColorAnim::Pulse { speed, alt_color } => {
+                    ui.add(egui::Slider::new(speed, 0.0..=4.0).text("pulse speed"));
+                    let mut ac = alt_color.to_srgba().to_f32_array_no_alpha();
+                    ui.horizontal(|ui| {
+                        egui::color_picker::color_edit_button_rgb(ui, &mut ac);
+                        ui.label("alt_color");
+                    });
+                    *alt_color = Color::srgb_from_array(ac);
+                }
+                EdgeColorAnim::Hue { speed } => {
+                    ui.add(egui::Slider::new(speed, 0.0..=2.0).text("hue speed"));
+                }
+            }
+
             ui.add(egui::Slider::new(&mut edge_detection.block_pixel, 1..=6).text("block_pixel"));
+            ui.add(field_slider(
+                &mut edge_detection.global_intensity,
+                "global_intensity",
+            ));
+            ui.add(field_slider(
+                &mut edge_detection.edge_opacity,
+                "edge_opacity",
+            ));
+            ui.add(field_slider(
+                &mut edge_detection.edge_emissive_strength,
+                "edge_emissive_strength",
+            ));
+
+            // Debug view
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("debug_view:");
+                for (view, label) in [
+                    (DebugView::Off, "Off"),
+                    (DebugView::EdgeDirection, "Edge direction"),
+                    (DebugView::DistortionField, "Distortion field"),
+                    (DebugView::ChannelColors, "Channel colors"),
+                    (DebugView::EdgeStrengthHeatmap, "Edge strength heatmap"),
+                    (DebugView::Normals, "Normals"),
+                ] {
+                    if ui
+                        .selectable_label(edge_detection.debug_view == view, label)
+                        .clicked()
+                    {
+                        edge_detection.debug_view = view;
+                    }
+                }
+            });
         });
     });
 }
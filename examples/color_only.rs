@@ -0,0 +1,79 @@
+//! Sketch-style filter over a scene using only the color buffer.
+//!
+//! `EdgeDetection::color_only()` disables depth/normal detection, so no
+//! `DepthPrepass`/`NormalPrepass` is added to the camera and the pass binds
+//! no prepass textures at all.
+
+use bevy::prelude::*;
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+#[derive(Component)]
+struct Shape;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let shapes = [
+        meshes.add(Cuboid::default()),
+        meshes.add(Torus::default()),
+        meshes.add(Sphere::default().mesh().uv(32, 18)),
+        meshes.add(Cone::default()),
+    ];
+    let colors = [
+        Color::srgb(0.9, 0.4, 0.3),
+        Color::srgb(0.3, 0.7, 0.9),
+        Color::srgb(0.9, 0.8, 0.3),
+        Color::srgb(0.5, 0.9, 0.4),
+    ];
+
+    for (i, (shape, color)) in shapes.into_iter().zip(colors).enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(materials.add(color)),
+            Transform::from_xyz(-4.5 + i as f32 * 3.0, 1.0, 0.0),
+            Shape,
+        ));
+    }
+
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(30.0, 30.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+    ));
+
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        Msaa::Off,
+        EdgeDetection {
+            color_threshold: 0.15,
+            uv_distortion_frequency: Vec2::splat(1.0),
+            uv_distortion_strength: Vec2::splat(0.003),
+            ..EdgeDetection::color_only()
+        },
+    ));
+}
+
+fn rotate(mut query: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_secs() / 2.);
+    }
+}
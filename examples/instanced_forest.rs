@@ -0,0 +1,92 @@
+//! Outlines on thousands of instanced meshes.
+//!
+//! Every tree shares one trunk mesh, one crown mesh and one material each, so
+//! Bevy batches them into GPU-instanced draws in the main pass and in the
+//! depth/normal prepasses alike. Edge detection is screen-space and only reads
+//! the prepass textures, so every instance gets the same outlines as a single
+//! mesh would, including silhouettes between overlapping instances.
+
+use bevy::prelude::*;
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin};
+
+const GRID: i32 = 50;
+const SPACING: f32 = 2.5;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, orbit)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let trunk = meshes.add(Cylinder::new(0.15, 1.0));
+    let crown = meshes.add(Cone::new(0.8, 2.0));
+    let bark = materials.add(Color::srgb(0.45, 0.3, 0.2));
+    let leaves = materials.add(Color::srgb(0.3, 0.6, 0.3));
+
+    // GRID x GRID trees, jittered so the rows don't line up.
+    for x in -GRID / 2..GRID / 2 {
+        for z in -GRID / 2..GRID / 2 {
+            let jitter = Vec2::new(
+                (x as f32 * 12.9898 + z as f32 * 78.233).sin().fract(),
+                (x as f32 * 39.346 + z as f32 * 11.135).sin().fract(),
+            );
+            let position = Vec3::new(
+                (x as f32 + jitter.x) * SPACING,
+                0.0,
+                (z as f32 + jitter.y) * SPACING,
+            );
+            let scale = 0.8 + 0.4 * jitter.x.abs();
+
+            commands
+                .spawn((
+                    Transform::from_translation(position).with_scale(Vec3::splat(scale)),
+                    Visibility::default(),
+                ))
+                .with_children(|tree| {
+                    tree.spawn((
+                        Mesh3d(trunk.clone()),
+                        MeshMaterial3d(bark.clone()),
+                        Transform::from_xyz(0.0, 0.5, 0.0),
+                    ));
+                    tree.spawn((
+                        Mesh3d(crown.clone()),
+                        MeshMaterial3d(leaves.clone()),
+                        Transform::from_xyz(0.0, 2.0, 0.0),
+                    ));
+                });
+        }
+    }
+
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(200.0, 200.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.55, 0.5, 0.4))),
+    ));
+
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 12.0, 40.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+        EdgeDetection::default(),
+    ));
+}
+
+fn orbit(mut camera: Query<&mut Transform, With<Camera3d>>, time: Res<Time>) {
+    for mut transform in &mut camera {
+        let angle = time.elapsed_secs() * 0.1;
+        *transform = Transform::from_xyz(40.0 * angle.sin(), 12.0, 40.0 * angle.cos())
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
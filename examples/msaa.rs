@@ -0,0 +1,80 @@
+//! Outlines with and without MSAA.
+//!
+//! Regression scene for the multisampled prepass resolve: with the default
+//! `NormalResolve::Average`, edge thickness and placement should look the same
+//! with `Msaa::Off` and `Msaa::Sample4`. Press `M` to toggle MSAA and `R` to
+//! cycle the resolve strategy; both are logged.
+
+use bevy::prelude::*;
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin, NormalResolve};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_msaa, cycle_resolve))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = materials.add(Color::srgb(0.3, 0.7, 0.9));
+    let shapes = [
+        meshes.add(Cuboid::default()),
+        meshes.add(Torus::default()),
+        meshes.add(Sphere::default().mesh().uv(32, 18)),
+        meshes.add(Cylinder::default()),
+    ];
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(-3.0 + i as f32 * 2.0, 1.0, 0.0)
+                .with_rotation(Quat::from_rotation_y(0.4)),
+        ));
+    }
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 8.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        Msaa::Sample4,
+        EdgeDetection::default(),
+    ));
+}
+
+fn toggle_msaa(keys: Res<ButtonInput<KeyCode>>, mut msaa: Single<&mut Msaa, With<Camera3d>>) {
+    if !keys.just_pressed(KeyCode::KeyM) {
+        return;
+    }
+    **msaa = match **msaa {
+        Msaa::Off => Msaa::Sample4,
+        _ => Msaa::Off,
+    };
+    info!("msaa: {:?}", **msaa);
+}
+
+fn cycle_resolve(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut edge_detection: Single<&mut EdgeDetection, With<Camera3d>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+    edge_detection.normal_resolve = match edge_detection.normal_resolve {
+        NormalResolve::Average => NormalResolve::Sample,
+        NormalResolve::Sample => NormalResolve::Dominant,
+        NormalResolve::Dominant => NormalResolve::Average,
+    };
+    info!("normal_resolve: {:?}", edge_detection.normal_resolve);
+}
@@ -0,0 +1,116 @@
+//! Normal-colored outlines under a rotating camera, in view or world space.
+//!
+//! The camera orbits a few shapes whose edges are colored from their normals
+//! through `EdgeColorFromNormal`. The outlines themselves don't change as the
+//! camera rotates: normal edges compare normals by distance, which the rotation
+//! doesn't affect. Only the colors depend on `normal_space`: with `View` they
+//! follow the screen (matcap), with `World` each surface orientation keeps its
+//! color. Press `Space` to switch; the space is logged.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_edge_detection_outline::{
+    EdgeColorFromNormal, EdgeDetection, EdgeDetectionPlugin, NormalSpace,
+};
+
+const ORBIT_RADIUS: f32 = 9.0;
+/// Radians per second.
+const ORBIT_SPEED: f32 = 0.4;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (orbit_camera, toggle_normal_space))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let material = materials.add(Color::srgb(0.85, 0.85, 0.8));
+    let shapes = [
+        meshes.add(Cuboid::default()),
+        meshes.add(Torus::default()),
+        meshes.add(Sphere::default().mesh().uv(32, 18)),
+    ];
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(-2.5 + i as f32 * 2.5, 1.0, 0.0),
+        ));
+    }
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(material),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, ORBIT_RADIUS).looking_at(Vec3::Y, Vec3::Y),
+        Msaa::Off,
+        EdgeDetection {
+            depth_thickness: 3.0,
+            normal_thickness: 3.0,
+            ..default()
+        },
+        EdgeColorFromNormal(images.add(normal_lookup_image())),
+    ));
+}
+
+/// Lookup image: red along x, green along y, so the edge color reads as the
+/// normal's xy in the selected space.
+fn normal_lookup_image() -> Image {
+    const SIZE: u32 = 64;
+    let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let u = x as f32 / (SIZE - 1) as f32;
+            let v = 1.0 - y as f32 / (SIZE - 1) as f32;
+            data.extend_from_slice(&[(u * 255.0) as u8, (v * 255.0) as u8, 64, 255]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+fn orbit_camera(time: Res<Time>, mut camera: Single<&mut Transform, With<Camera3d>>) {
+    let angle = time.elapsed_secs() * ORBIT_SPEED;
+    let position = Vec3::new(angle.sin() * ORBIT_RADIUS, 4.0, angle.cos() * ORBIT_RADIUS);
+    **camera = Transform::from_translation(position).looking_at(Vec3::Y, Vec3::Y);
+}
+
+fn toggle_normal_space(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut edge_detection: Single<&mut EdgeDetection>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+    edge_detection.normal_space = match edge_detection.normal_space {
+        NormalSpace::View => NormalSpace::World,
+        NormalSpace::World => NormalSpace::View,
+    };
+    info!("normal_space: {:?}", edge_detection.normal_space);
+}
@@ -0,0 +1,82 @@
+//! Reacting to cameras gaining or losing outlines with observers.
+//!
+//! `EdgeDetection` is a regular component, so `On<Add, EdgeDetection>` /
+//! `On<Remove, EdgeDetection>` observers fire like for any other component; the
+//! plugin's own render-world syncing runs alongside them. Press `E` to toggle
+//! the effect on the camera and watch the log.
+
+use bevy::prelude::*;
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_observer(on_edge_detection_added)
+        .add_observer(on_edge_detection_removed)
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_edge_detection)
+        .run();
+}
+
+/// Runs when `EdgeDetection` is inserted on an entity that didn't have it,
+/// e.g. for editor tooling that tracks outlined cameras.
+fn on_edge_detection_added(add: On<Add, EdgeDetection>, query: Query<&EdgeDetection>) {
+    let Ok(edge_detection) = query.get(add.entity) else {
+        return;
+    };
+    info!(
+        "edge detection added to {}: operator {:?}, depth {}, normal {}, color {}",
+        add.entity,
+        edge_detection.operator,
+        edge_detection.enable_depth,
+        edge_detection.enable_normal,
+        edge_detection.enable_color,
+    );
+}
+
+fn on_edge_detection_removed(remove: On<Remove, EdgeDetection>) {
+    info!("edge detection removed from {}", remove.entity);
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Torus::default())),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.7, 0.9))),
+        Transform::from_xyz(0.0, 1.0, 0.0),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 6.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        Msaa::Off,
+        EdgeDetection::default(),
+    ));
+}
+
+fn toggle_edge_detection(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    camera: Single<(Entity, Has<EdgeDetection>), With<Camera3d>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+    let (entity, has_edge_detection) = *camera;
+    if has_edge_detection {
+        commands.entity(entity).remove::<EdgeDetection>();
+    } else {
+        commands.entity(entity).insert(EdgeDetection::default());
+    }
+}
@@ -0,0 +1,73 @@
+//! Outlines under an orthographic camera, over a tilted ground plane.
+//!
+//! Regression scene for the steep-angle compensation: with an orthographic
+//! projection the depth step across a tilted plane doesn't grow with distance,
+//! so the plane must stay free of stray outlines at every distance while the
+//! boxes standing on it keep theirs. Press `Space` to toggle the tilt.
+
+use bevy::{camera::ScalingMode, prelude::*};
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_tilt)
+        .run();
+}
+
+#[derive(Component)]
+struct Ground;
+
+const TILT: f32 = 0.35;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .spawn((
+            Mesh3d(meshes.add(Plane3d::default().mesh().size(60.0, 60.0))),
+            MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+            Transform::from_rotation(Quat::from_rotation_x(TILT)),
+            Ground,
+        ))
+        .with_children(|ground| {
+            let cube = meshes.add(Cuboid::default());
+            let material = materials.add(Color::srgb(0.3, 0.7, 0.9));
+            for z in -4..=4 {
+                ground.spawn((
+                    Mesh3d(cube.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_xyz(0.0, 0.5, z as f32 * 6.0),
+                ));
+            }
+        });
+
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Projection::from(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: 20.0,
+            },
+            ..OrthographicProjection::default_3d()
+        }),
+        Transform::from_xyz(15.0, 15.0, 15.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+        EdgeDetection::default(),
+    ));
+}
+
+fn toggle_tilt(keys: Res<ButtonInput<KeyCode>>, mut ground: Single<&mut Transform, With<Ground>>) {
+    if keys.just_pressed(KeyCode::Space) {
+        let tilted = ground.rotation != Quat::IDENTITY;
+        ground.rotation = Quat::from_rotation_x(if tilted { 0.0 } else { TILT });
+    }
+}
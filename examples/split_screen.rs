@@ -0,0 +1,95 @@
+//! Two split-screen cameras with independent outline settings.
+//!
+//! Both cameras render the same scene into the left and right halves of the
+//! window. The left one draws thin black Roberts Cross outlines, the right one
+//! thick red Sobel outlines: each view's settings live in their own uniform slot,
+//! so neither camera picks up the other's.
+
+use bevy::{camera::Viewport, prelude::*, window::WindowResized};
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin, EdgeOperator};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, set_camera_viewports)
+        .run();
+}
+
+/// Which half of the window a camera renders to: 0 = left, 1 = right.
+#[derive(Component)]
+struct Half(u32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = materials.add(Color::srgb(0.3, 0.7, 0.9));
+    let shapes = [
+        meshes.add(Cuboid::default()),
+        meshes.add(Torus::default()),
+        meshes.add(Sphere::default().mesh().uv(32, 18)),
+    ];
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(-2.5 + i as f32 * 2.5, 1.0, 0.0),
+        ));
+    }
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(20.0, 20.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(-3.0, 4.0, 8.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        Msaa::Off,
+        Half(0),
+        EdgeDetection::default(),
+    ));
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            order: 1,
+            ..default()
+        },
+        Transform::from_xyz(3.0, 4.0, 8.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        Msaa::Off,
+        Half(1),
+        EdgeDetection {
+            operator: EdgeOperator::Sobel,
+            edge_color: Color::srgb(0.9, 0.1, 0.1),
+            depth_thickness: 3.0,
+            normal_thickness: 3.0,
+            ..default()
+        },
+    ));
+}
+
+fn set_camera_viewports(
+    window: Single<&Window>,
+    mut resize_events: MessageReader<WindowResized>,
+    mut cameras: Query<(&Half, &mut Camera)>,
+) {
+    // Also runs on the first frame, before any resize.
+    if resize_events.read().count() == 0 && cameras.iter().all(|(_, c)| c.viewport.is_some()) {
+        return;
+    }
+    let size = window.physical_size();
+    let half = UVec2::new(size.x / 2, size.y);
+    for (Half(index), mut camera) in &mut cameras {
+        camera.viewport = Some(Viewport {
+            physical_position: UVec2::new(index * half.x, 0),
+            physical_size: half.max(UVec2::ONE),
+            ..default()
+        });
+    }
+}
@@ -0,0 +1,110 @@
+//! Two outlined cameras composited onto the same window.
+//!
+//! The world camera draws the floor and pillars with black outlines. The
+//! foreground camera renders only layer 1 (the spinning shapes), clears to
+//! transparent and alpha-blends its output over the world camera with
+//! `CameraOutputMode::Write`. Its red outlines are half transparent: each edge
+//! pass runs on its own camera's main texture before compositing, so the edge
+//! alpha is applied exactly once by the camera blend.
+
+use bevy::{
+    camera::{CameraOutputMode, visibility::RenderLayers},
+    prelude::*,
+    render::render_resource::BlendState,
+};
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EdgeDetectionPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+#[derive(Component)]
+struct Shape;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(30.0, 30.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.85, 0.85, 0.8))),
+    ));
+
+    for i in 0..4 {
+        commands.spawn((
+            Mesh3d(meshes.add(Cylinder::new(0.3, 3.0))),
+            MeshMaterial3d(materials.add(Color::srgb(0.6, 0.6, 0.7))),
+            Transform::from_xyz(-4.5 + i as f32 * 3.0, 1.5, -3.0),
+        ));
+    }
+
+    let shapes = [
+        meshes.add(Cuboid::default()),
+        meshes.add(Torus::default()),
+        meshes.add(Sphere::default().mesh().uv(32, 18)),
+    ];
+    for (i, shape) in shapes.into_iter().enumerate() {
+        commands.spawn((
+            Mesh3d(shape),
+            MeshMaterial3d(materials.add(Color::srgb(0.3, 0.7, 0.9))),
+            Transform::from_xyz(-3.0 + i as f32 * 3.0, 1.0, 0.0),
+            RenderLayers::layer(1),
+            Shape,
+        ));
+    }
+
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+        RenderLayers::from_layers(&[0, 1]),
+    ));
+
+    let camera_transform =
+        Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y);
+
+    // World camera (layer 0).
+    commands.spawn((
+        Camera3d::default(),
+        camera_transform,
+        Msaa::Off,
+        EdgeDetection::default(),
+    ));
+
+    // Foreground camera (layer 1), blended over the world camera.
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::Custom(Color::NONE),
+            output_mode: CameraOutputMode::Write {
+                blend_state: Some(BlendState::ALPHA_BLENDING),
+                clear_color: ClearColorConfig::None,
+            },
+            ..default()
+        },
+        camera_transform,
+        Msaa::Off,
+        RenderLayers::layer(1),
+        EdgeDetection {
+            edge_color: Color::srgba(0.9, 0.1, 0.1, 0.5),
+            depth_thickness: 2.0,
+            normal_thickness: 2.0,
+            ..default()
+        },
+    ));
+}
+
+fn rotate(mut query: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_secs() / 2.);
+    }
+}
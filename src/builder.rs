@@ -0,0 +1,119 @@
+//! Chainable `with_*` setters for [`EdgeDetection`].
+//!
+//! ```ignore
+//! EdgeDetection::default()
+//!     .with_depth_threshold(2.0)
+//!     .with_edge_color(Color::srgb(1.0, 0.0, 0.0))
+//!     .with_normal_detection(false)
+//! ```
+//!
+//! Every field has one; the fields stay public, so struct-literal construction
+//! works as before. Setters for `Option` fields take the inner value and set
+//! `Some`; assign `None` to the field directly to clear it.
+
+use bevy::prelude::*;
+
+use crate::{
+    BorderMode, ColorEdgeSpace, DebugView, DepthEdgeMode, DepthNormalMode, EdgeBlendMode,
+    EdgeColorAnim, EdgeColorMode, EdgeDetection, EdgeKernel, EdgeOperator, NormalEncoding,
+    NormalResolve, NormalSource, NormalSpace,
+};
+
+macro_rules! with_setters {
+    ($($method:ident => $field:ident: $ty:ty $(, $some:ident)?;)*) => {
+        impl EdgeDetection {
+            $(
+                #[doc = concat!("Sets [`EdgeDetection::", stringify!($field), "`].")]
+                #[must_use]
+                pub fn $method(mut self, $field: $ty) -> Self {
+                    self.$field = with_setters!(@wrap $field $(, $some)?);
+                    self
+                }
+            )*
+        }
+    };
+    (@wrap $value:ident) => { $value };
+    (@wrap $value:ident, $some:ident) => { $some($value) };
+}
+
+with_setters! {
+    with_enabled => enabled: bool;
+    with_depth_threshold => depth_threshold: f32;
+    with_depth_threshold_world_units => depth_threshold_world_units: f32, Some;
+    with_normal_threshold => normal_threshold: f32;
+    with_crease_threshold => crease_threshold: f32, Some;
+    with_color_threshold => color_threshold: f32;
+    with_color_edge_space => color_edge_space: ColorEdgeSpace;
+    with_skip_transparent => skip_transparent: bool;
+    with_depth_thickness => depth_thickness: f32;
+    with_normal_thickness => normal_thickness: f32;
+    with_color_thickness => color_thickness: f32;
+    with_steep_angle_threshold => steep_angle_threshold: f32;
+    with_steep_angle_multiplier => steep_angle_multiplier: f32;
+    with_depth_bias => depth_bias: f32;
+    with_depth_prefilter => depth_prefilter: u32;
+    with_uv_distortion_frequency => uv_distortion_frequency: Vec2;
+    with_uv_distortion_strength => uv_distortion_strength: Vec2;
+    with_uv_distortion_speed => uv_distortion_speed: Vec2;
+    with_edge_color => edge_color: Color;
+    with_silhouette_color => silhouette_color: Color, Some;
+    with_crease_color => crease_color: Color, Some;
+    with_color_edge_color => color_edge_color: Color, Some;
+    with_depth_detection => enable_depth: bool;
+    with_normal_detection => enable_normal: bool;
+    with_color_detection => enable_color: bool;
+    with_block_pixel => block_pixel: u32;
+    with_flat_rejection_threshold => flat_rejection_threshold: f32;
+    with_operator => operator: EdgeOperator;
+    with_kernel => kernel: EdgeKernel;
+    with_kernel_radius => kernel_radius: u32;
+    with_transparent_background => transparent_background: bool;
+    with_thin_feature_recovery => thin_feature_recovery: bool;
+    with_depth_edge_mode => depth_edge_mode: DepthEdgeMode;
+    with_nearest_filtering => nearest_filtering: bool;
+    with_edge_color_animation => edge_color_animation: EdgeColorAnim;
+    with_output_bit_depth => output_bit_depth: UVec3, Some;
+    with_depth_normal_mode => depth_normal_mode: DepthNormalMode;
+    with_plane_threshold => plane_threshold: f32;
+    with_resolution_independent_thickness => resolution_independent_thickness: bool;
+    with_distortion_phase => distortion_phase: f32;
+    with_distortion_rotation => distortion_rotation: f32;
+    with_custom_detection => enable_custom: bool;
+    with_custom_threshold => custom_threshold: f32;
+    with_custom_thickness => custom_thickness: f32;
+    with_auto_contrast => auto_contrast: bool;
+    with_output_edge_direction => output_edge_direction: bool;
+    with_output_edge_mask => output_edge_mask: bool;
+    with_debug_view => debug_view: DebugView;
+    with_flat_background => flat_background: Color, Some;
+    with_silhouette_halo => silhouette_halo: bool;
+    with_outline_against_background => outline_against_background: bool;
+    with_coverage_thickness => coverage_thickness: bool;
+    with_threshold_vignette => threshold_vignette: f32;
+    with_vignette_radius => vignette_radius: f32;
+    with_border_outline => border_outline: BorderMode;
+    with_viewport_rect => viewport_rect: Rect, Some;
+    with_depth_threshold_far => depth_threshold_far: f32, Some;
+    with_depth_threshold_ramp => depth_threshold_ramp: Vec2;
+    with_motion_gate => enable_motion_gate: bool;
+    with_motion_threshold => motion_threshold: f32;
+    with_normal_resolve => normal_resolve: NormalResolve;
+    with_normal_source => normal_source: NormalSource;
+    with_normal_encoding => normal_encoding: NormalEncoding;
+    with_normal_space => normal_space: NormalSpace;
+    with_global_intensity => global_intensity: f32;
+    with_edge_opacity => edge_opacity: f32;
+    with_edge_emissive_strength => edge_emissive_strength: f32;
+    with_blend_mode => blend_mode: EdgeBlendMode;
+    with_edge_fade_start => edge_fade_start: f32;
+    with_edge_fade_end => edge_fade_end: f32;
+    with_resolution_scale => resolution_scale: f32;
+    with_edge_dilation => edge_dilation: u32;
+    with_rim_strength => rim_strength: f32;
+    with_edge_smoothness => edge_smoothness: f32;
+    with_temporal_blend => temporal_blend: f32;
+    with_thickness_depth_falloff => thickness_depth_falloff: f32;
+    with_thickness_axis_scale => thickness_axis_scale: Vec2;
+    with_edge_color_mode => edge_color_mode: EdgeColorMode;
+    with_edge_color_secondary => edge_color_secondary: Color;
+}
@@ -0,0 +1,118 @@
+//! One-shot depth threshold calibration from the scene's depth range.
+//!
+//! Insert [`AutoCalibrateEdgeDetection`] on a camera with [`EdgeDetection`] and, on
+//! the next `PostUpdate`, its `depth_threshold_world_units` is set from the
+//! view-depth range of the meshes in that camera's frustum (see
+//! [`EdgeDetection::auto_calibrate`]). The range is in world units, so it sets the
+//! threshold in world units too, which means the same for every operator. The
+//! marker is then removed, so the calibration runs once per insertion.
+//!
+//! Unlike a depth readback, the range is reduced on the CPU from the bounds of the
+//! meshes in the camera's [`VisibleEntities`] (the frustum-culled list rendered
+//! for that camera): no GPU stall and done in one frame, but occluded meshes and
+//! the parts of a bounding sphere outside the frustum still widen the range. That
+//! is accurate enough for a starting point to tune from, not a per-frame
+//! adjustment — re-insert the marker after large scene changes if needed.
+
+use std::{any::TypeId, ops::Range};
+
+use bevy::{
+    camera::{
+        primitives::Aabb,
+        visibility::{VisibilitySystems, VisibleEntities},
+    },
+    prelude::*,
+};
+
+use crate::EdgeDetection;
+
+/// Fraction of the scene's depth range used as the calibrated
+/// `depth_threshold_world_units`.
+pub const AUTO_CALIBRATE_DEPTH_FRACTION: f32 = 0.05;
+
+/// Requests a one-shot depth threshold calibration for this camera.
+/// See the [module docs](self).
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct AutoCalibrateEdgeDetection;
+
+impl EdgeDetection {
+    /// Sets `depth_threshold_world_units` proportionally to the scene's view-depth
+    /// range (`near..far`, positive distances along the view direction, in world
+    /// units); `depth_threshold` is left as is but no longer used. Returns the
+    /// resulting [`Self::effective_depth_threshold`], the operator-dependent value
+    /// the shader compares against. A starting point for tuning, not a per-frame
+    /// adjustment.
+    pub fn auto_calibrate(&mut self, depth_range: Range<f32>) -> f32 {
+        let range = (depth_range.end - depth_range.start).max(0.0);
+        self.depth_threshold_world_units = Some((range * AUTO_CALIBRATE_DEPTH_FRACTION).max(0.01));
+        self.effective_depth_threshold()
+    }
+}
+
+pub(crate) struct EdgeDetectionCalibrationPlugin;
+
+impl Plugin for EdgeDetectionCalibrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<AutoCalibrateEdgeDetection>()
+            .add_systems(
+                PostUpdate,
+                auto_calibrate_edge_detection.after(VisibilitySystems::CheckVisibility),
+            );
+    }
+}
+
+fn auto_calibrate_edge_detection(
+    mut commands: Commands,
+    mut cameras: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &VisibleEntities,
+            &mut EdgeDetection,
+        ),
+        With<AutoCalibrateEdgeDetection>,
+    >,
+    meshes: Query<(&Aabb, &GlobalTransform), With<Mesh3d>>,
+) {
+    for (entity, camera_transform, visible, mut edge_detection) in &mut cameras {
+        let origin = camera_transform.translation();
+        let forward = camera_transform.forward();
+
+        let mut depth_range: Option<Range<f32>> = None;
+        // Only this camera's meshes: `ViewVisibility` would also count meshes
+        // visible to any other view.
+        let visible_meshes = visible.iter(TypeId::of::<Mesh3d>());
+        for (aabb, transform) in meshes.iter_many(visible_meshes) {
+            let center = transform.transform_point(aabb.center.into());
+            let radius = (transform.affine().matrix3 * Vec3::from(aabb.half_extents)).length();
+            let depth = (center - origin).dot(*forward);
+            if depth + radius <= 0.0 {
+                continue;
+            }
+            let near = (depth - radius).max(0.0);
+            let far = depth + radius;
+            depth_range = Some(match depth_range {
+                Some(range) => range.start.min(near)..range.end.max(far),
+                None => near..far,
+            });
+        }
+
+        commands
+            .entity(entity)
+            .remove::<AutoCalibrateEdgeDetection>();
+        let Some(depth_range) = depth_range else {
+            warn!("edge detection auto-calibration found no visible meshes in front of {entity}");
+            continue;
+        };
+        let threshold = edge_detection.auto_calibrate(depth_range.clone());
+        let world_units = edge_detection
+            .depth_threshold_world_units
+            .unwrap_or_default();
+        info!(
+            "edge detection auto-calibration for {entity}: depth range {:.2}..{:.2}, \
+             depth_threshold_world_units = {world_units:.3} (effective threshold {threshold:.3})",
+            depth_range.start, depth_range.end
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Color-buffer outlines for 2D cameras.
+//!
+//! 2D views have no depth or normal prepass, so [`EdgeDetection2dPlugin`] runs the
+//! same pass in the `Core2d` schedule with the color-only pipeline variant: only
+//! color edges (plus any [custom source](crate::EdgeDetectionCustomSource)) are
+//! detected, `enable_depth`/`enable_normal` and the other prepass-only settings are
+//! ignored, and the bind group layout has no depth/normal textures. Uniform-only
+//! settings (thresholds, thickness, colors, distortion, pixelation, ...) behave as
+//! on 3D cameras.
+
+use bevy::{
+    anti_alias::{fxaa::fxaa, smaa::smaa},
+    core_pipeline::{Core2d, Core2dSystems, tonemapping::tonemapping},
+    prelude::*,
+    render::RenderApp,
+};
+
+use crate::{EdgeDetectionPassSet, EdgeDetectionPlugin, edge_detection};
+
+/// Runs [`EdgeDetection`](crate::EdgeDetection) on `Camera2d` views, from the color
+/// buffer only. See the [module docs](self). Adds [`EdgeDetectionPlugin`] if it
+/// isn't already added.
+#[derive(Default)]
+pub struct EdgeDetection2dPlugin;
+
+/// Present in the main world when 2D cameras are supported.
+#[derive(Resource, Default)]
+pub(crate) struct EdgeDetection2dSupport;
+
+impl Plugin for EdgeDetection2dPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EdgeDetectionPlugin>() {
+            app.add_plugins(EdgeDetectionPlugin::default());
+        }
+        app.init_resource::<EdgeDetection2dSupport>();
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        // Same slot as in Core3d: after tonemapping, before anti-aliasing.
+        render_app.add_systems(
+            Core2d,
+            edge_detection
+                .after(tonemapping)
+                .before(fxaa)
+                .before(smaa)
+                .in_set(Core2dSystems::PostProcess)
+                .in_set(EdgeDetectionPassSet),
+        );
+    }
+}
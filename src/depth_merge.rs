@@ -0,0 +1,274 @@
+//! Depth edges across cameras that share a render target
+//! ([`EdgeDetectionDepthMerge`]).
+//!
+//! A scene split over several cameras drawing into one target (a first-person
+//! weapon camera over the world camera, layers composited by `order`) has one
+//! depth prepass per camera, and the edge pass only reads its own camera's: the
+//! silhouettes between layers never show up as depth edges. With an
+//! [`EdgeDetectionDepthMerge`] on the last camera, each frame its depth prepass
+//! and those of the listed cameras are merged into a [`ViewEdgeMergedDepth`]
+//! before the edge pass, keeping the nearest depth per pixel (the largest, with
+//! Bevy's reverse-Z). The edge pass then binds the merged texture wherever it
+//! would bind its own depth prepass, including the `depth_prefilter` pyramid and
+//! normals reconstructed from depth.
+//!
+//! Depths are only comparable when the cameras share the target size, the
+//! projection and the transform. Normals, color and the custom sources still
+//! come from the edge camera alone. Multisampled views aren't merged.
+
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::{
+        FullscreenShader, core_3d::CORE_3D_DEPTH_FORMAT, prepass::ViewPrepassTextures,
+    },
+    prelude::*,
+    render::{
+        Extract, Render, RenderApp, RenderSystems,
+        render_resource::{binding_types::texture_depth_2d, *},
+        renderer::{RenderContext, RenderDevice},
+        sync_world::RenderEntity,
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+    },
+};
+
+use crate::{EdgeDetection, EdgeDetectionPipelineId};
+
+/// Other cameras whose depth prepasses the depth edges of this [`EdgeDetection`]
+/// camera read, merged with its own. See the [module docs](self).
+///
+/// The listed cameras need a `DepthPrepass`, the same target, projection and
+/// transform as this camera, and a lower `order`, so their prepasses are filled
+/// when this camera's edge pass runs. Cameras without a depth prepass, or whose
+/// prepass size differs from this view's, are left out of the merge.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Clone)]
+pub struct EdgeDetectionDepthMerge(pub Vec<Entity>);
+
+/// Render entities of the cameras listed in a view's [`EdgeDetectionDepthMerge`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct EdgeDepthMergeSources(pub Vec<Entity>);
+
+/// Merged depth of views with an [`EdgeDetectionDepthMerge`], in the depth
+/// prepass format: the nearest depth of all merged prepasses per pixel.
+#[derive(Component, Clone)]
+pub struct ViewEdgeMergedDepth {
+    pub texture: CachedTexture,
+}
+
+pub(crate) struct EdgeDetectionDepthMergePlugin;
+
+impl Plugin for EdgeDetectionDepthMergePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "depth_merge.wgsl");
+
+        app.register_type::<EdgeDetectionDepthMerge>();
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .add_systems(ExtractSchedule, extract_edge_depth_merge_sources)
+            .add_systems(
+                Render,
+                prepare_edge_merged_depth.in_set(RenderSystems::PrepareResources),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<EdgeDepthMergePipeline>();
+    }
+}
+
+fn extract_edge_depth_merge_sources(
+    mut commands: Commands,
+    query: Extract<Query<(RenderEntity, Option<&EdgeDetectionDepthMerge>), With<EdgeDetection>>>,
+    render_entities: Extract<Query<&RenderEntity>>,
+) {
+    for (render_entity, depth_merge) in &query {
+        let Ok(mut entity_commands) = commands.get_entity(render_entity) else {
+            continue;
+        };
+        match depth_merge {
+            Some(depth_merge) if !depth_merge.0.is_empty() => {
+                let sources = depth_merge
+                    .0
+                    .iter()
+                    .filter_map(|&camera| render_entities.get(camera).ok())
+                    .map(|source| source.id())
+                    .filter(|&source| source != render_entity)
+                    .collect();
+                entity_commands.insert(EdgeDepthMergeSources(sources));
+            }
+            _ => {
+                entity_commands.remove::<EdgeDepthMergeSources>();
+            }
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct EdgeDepthMergePipeline {
+    pub layout: BindGroupLayoutDescriptor,
+    pub pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for EdgeDepthMergePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = BindGroupLayoutDescriptor::new(
+            "edge_detection: depth merge bind_group_layout",
+            &[texture_depth_2d().build(0, ShaderStages::FRAGMENT)],
+        );
+        let shader = load_embedded_asset!(world, "depth_merge.wgsl");
+        let fullscreen_shader = world.resource::<FullscreenShader>().clone();
+
+        let pipeline_id =
+            world
+                .resource::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("edge_detection: depth merge pipeline".into()),
+                    layout: vec![layout.clone()],
+                    vertex: fullscreen_shader.to_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader,
+                        shader_defs: vec![],
+                        entry_point: Some("merge".into()),
+                        targets: vec![],
+                    }),
+                    primitive: default(),
+                    // Reverse-Z: the nearest depth is the largest.
+                    depth_stencil: Some(DepthStencilState {
+                        format: CORE_3D_DEPTH_FORMAT,
+                        depth_write_enabled: true,
+                        depth_compare: CompareFunction::GreaterEqual,
+                        stencil: default(),
+                        bias: default(),
+                    }),
+                    multisample: default(),
+                    immediate_size: 0,
+                    zero_initialize_workgroup_memory: false,
+                });
+
+        Self {
+            layout,
+            pipeline_id,
+        }
+    }
+}
+
+/// Allocates a [`ViewEdgeMergedDepth`] for views with merge sources that read a
+/// single-sampled depth prepass, and drops it from the others.
+fn prepare_edge_merged_depth(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(
+        Entity,
+        &EdgeDetectionPipelineId,
+        &ViewTarget,
+        Option<&EdgeDepthMergeSources>,
+        Has<ViewEdgeMergedDepth>,
+    )>,
+) {
+    for (entity, pipeline_id, view_target, sources, has_merged_depth) in &query {
+        let layout_key = pipeline_id.1;
+        let merge = sources.is_some_and(|sources| !sources.0.is_empty())
+            && !layout_key.color_only
+            && !layout_key.multisampled;
+        if sources.is_some() && layout_key.multisampled {
+            warn_once!(
+                "EdgeDetectionDepthMerge is ignored on multisampled cameras: use Msaa::Off \
+                 on the edge camera and the merged cameras"
+            );
+        }
+        if !merge {
+            if has_merged_depth {
+                commands.entity(entity).remove::<ViewEdgeMergedDepth>();
+            }
+            continue;
+        }
+
+        let size = view_target.main_texture().size();
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("edge_detection: merged depth texture"),
+                size: Extent3d {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: CORE_3D_DEPTH_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewEdgeMergedDepth { texture });
+    }
+}
+
+/// Fills `merged_depth` with the nearest of `depth` (the view's own prepass) and
+/// the depth prepasses of `sources`. Sources without a single-sampled depth
+/// prepass of the merged size are skipped. Returns `false`, encoding nothing,
+/// while the pipeline isn't compiled yet.
+pub(crate) fn merge_depth<'a>(
+    ctx: &mut RenderContext,
+    pipeline_cache: &PipelineCache,
+    merge_pipeline: &EdgeDepthMergePipeline,
+    merged_depth: &ViewEdgeMergedDepth,
+    depth: &'a TextureView,
+    sources: impl IntoIterator<Item = &'a ViewPrepassTextures>,
+) -> bool {
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(merge_pipeline.pipeline_id) else {
+        return false;
+    };
+
+    let size = merged_depth.texture.texture.size();
+    let source_views = sources
+        .into_iter()
+        .filter_map(|prepass| prepass.depth.as_ref())
+        .filter(|source| {
+            source.texture.texture.size() == size && source.texture.texture.sample_count() == 1
+        })
+        .map(|source| &source.texture.default_view);
+    let layout = pipeline_cache.get_bind_group_layout(&merge_pipeline.layout);
+    let bind_groups: Vec<_> = core::iter::once(depth)
+        .chain(source_views)
+        .map(|view| {
+            ctx.render_device().create_bind_group(
+                "edge_detection_depth_merge_bind_group",
+                &layout,
+                &BindGroupEntries::single(view),
+            )
+        })
+        .collect();
+
+    let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("edge_detection_depth_merge_pass"),
+        color_attachments: &[],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+            view: &merged_depth.texture.default_view,
+            // Reverse-Z: 0 is the far plane, so every source depth passes.
+            depth_ops: Some(Operations {
+                load: LoadOp::Clear(0.0),
+                store: StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    });
+    render_pass.set_render_pipeline(pipeline);
+    for bind_group in &bind_groups {
+        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+    true
+}
@@ -0,0 +1,11 @@
+//! Depth merge for `EdgeDetectionDepthMerge`: writes one depth prepass into the
+//! merged depth, whose depth test keeps the nearest (largest, reverse-Z) value.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var depth_prepass_texture: texture_depth_2d;
+
+@fragment
+fn merge(in: FullscreenVertexOutput) -> @builtin(frag_depth) f32 {
+    return textureLoad(depth_prepass_texture, vec2i(in.position.xy), 0);
+}
@@ -0,0 +1,359 @@
+//! Downsampled depth for depth edges (`EdgeDetection::depth_prefilter`).
+//!
+//! High-frequency geometry (foliage, grates) gives every other pixel a depth
+//! discontinuity, and the depth detector outlines all of them. With
+//! `depth_prefilter` above 0, each frame the depth prepass is linearized to view z
+//! into the first level of a [`ViewEdgeDepthPyramid`], and every further level
+//! averages 2x2 texels of the previous one. The depth detector then samples the
+//! last level (bilinearly, with its offsets scaled to that level's texels) instead
+//! of the prepass: small features average out and the remaining outlines are
+//! softer and wider. Background texels are excluded from the averages, so objects
+//! don't fade into the sky.
+//!
+//! Only depth edges use the pyramid; normals, the depth fade and custom sources
+//! still read the full-resolution prepass. Multisampled prepasses are linearized
+//! from their first sample.
+
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::FullscreenShader,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        render_resource::{
+            binding_types::{
+                texture_2d, texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer,
+            },
+            *,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::{ViewTarget, ViewUniform},
+    },
+};
+
+use crate::{EdgeDetection, EdgeDetectionPipelineId, EdgeDetectionSystems};
+
+/// Format of [`ViewEdgeDepthPyramid`]: view z in R, 0 for the background.
+pub const EDGE_DEPTH_PYRAMID_TEXTURE_FORMAT: TextureFormat = TextureFormat::R32Float;
+
+/// Largest number of downsample steps.
+pub const MAX_DEPTH_PREFILTER: u32 = 4;
+
+/// Linear depth mip chain of views with `depth_prefilter > 0`: level 0 is at the
+/// view resolution, each further level halves it.
+#[derive(Component, Clone)]
+pub struct ViewEdgeDepthPyramid {
+    pub texture: CachedTexture,
+    /// One view per mip level; the last is the level depth edges sample.
+    pub mips: Vec<TextureView>,
+}
+
+/// Linearize and downsample pipelines of a view with a [`ViewEdgeDepthPyramid`].
+#[derive(Component, Clone, Copy)]
+pub struct EdgeDepthPrefilterPipelineIds {
+    pub linearize: CachedRenderPipelineId,
+    pub downsample: CachedRenderPipelineId,
+    /// Whether `linearize` reads a multisampled depth prepass.
+    pub multisampled: bool,
+}
+
+pub(crate) struct EdgeDetectionDepthPrefilterPlugin;
+
+impl Plugin for EdgeDetectionDepthPrefilterPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "depth_prefilter.wgsl");
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<EdgeDepthPrefilterPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_edge_depth_prefilter_pipelines
+                        .in_set(EdgeDetectionSystems::PreparePipelines)
+                        .after(crate::prepare_edge_detection_pipelines),
+                    prepare_edge_depth_pyramids.in_set(RenderSystems::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<EdgeDepthPrefilterPipeline>();
+    }
+}
+
+/// Number of downsample steps these settings use, at most [`MAX_DEPTH_PREFILTER`].
+pub(crate) fn prefilter_levels(edge_detection: &EdgeDetection) -> u32 {
+    edge_detection.depth_prefilter.min(MAX_DEPTH_PREFILTER)
+}
+
+#[derive(Resource)]
+pub struct EdgeDepthPrefilterPipeline {
+    pub linearize_layout: BindGroupLayoutDescriptor,
+    pub linearize_layout_multisampled: BindGroupLayoutDescriptor,
+    pub downsample_layout: BindGroupLayoutDescriptor,
+    pub shader: Handle<Shader>,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl EdgeDepthPrefilterPipeline {
+    fn linearize_layout(&self, multisampled: bool) -> &BindGroupLayoutDescriptor {
+        if multisampled {
+            &self.linearize_layout_multisampled
+        } else {
+            &self.linearize_layout
+        }
+    }
+}
+
+impl FromWorld for EdgeDepthPrefilterPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let stages = ShaderStages::FRAGMENT;
+        let linearize_layout = BindGroupLayoutDescriptor::new(
+            "edge_detection: depth linearize bind_group_layout",
+            &[
+                texture_depth_2d().build(0, stages),
+                uniform_buffer::<ViewUniform>(true).build(1, stages),
+            ],
+        );
+        let linearize_layout_multisampled = BindGroupLayoutDescriptor::new(
+            "edge_detection: depth linearize multisampled bind_group_layout",
+            &[
+                texture_depth_2d_multisampled().build(0, stages),
+                uniform_buffer::<ViewUniform>(true).build(1, stages),
+            ],
+        );
+        let downsample_layout = BindGroupLayoutDescriptor::new(
+            "edge_detection: depth downsample bind_group_layout",
+            &[texture_2d(TextureSampleType::Float { filterable: false }).build(0, stages)],
+        );
+        Self {
+            linearize_layout,
+            linearize_layout_multisampled,
+            downsample_layout,
+            shader: load_embedded_asset!(world, "depth_prefilter.wgsl"),
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EdgeDepthPrefilterKey {
+    /// Depth prepass to view z, into level 0.
+    Linearize { multisampled: bool },
+    /// 2x2 average of the previous level.
+    Downsample,
+}
+
+impl SpecializedRenderPipeline for EdgeDepthPrefilterPipeline {
+    type Key = EdgeDepthPrefilterKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, shader_defs, entry_point) = match key {
+            EdgeDepthPrefilterKey::Linearize { multisampled } => {
+                let mut shader_defs = vec!["LINEARIZE".into()];
+                if multisampled {
+                    shader_defs.push("MULTISAMPLED".into());
+                }
+                (
+                    self.linearize_layout(multisampled),
+                    shader_defs,
+                    "linearize",
+                )
+            }
+            EdgeDepthPrefilterKey::Downsample => (&self.downsample_layout, vec![], "downsample"),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("edge_detection: depth prefilter pipeline".into()),
+            layout: vec![layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs,
+                entry_point: Some(entry_point.into()),
+                targets: vec![Some(ColorTargetState {
+                    format: EDGE_DEPTH_PYRAMID_TEXTURE_FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            immediate_size: 0,
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// Specializes the prefilter pipelines of views whose edge pipeline is keyed with
+/// `depth_prefilter`, reading the layout key the edge pipeline was specialized with.
+fn prepare_edge_depth_prefilter_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<EdgeDepthPrefilterPipeline>>,
+    prefilter_pipeline: Res<EdgeDepthPrefilterPipeline>,
+    query: Query<(
+        Entity,
+        &EdgeDetectionPipelineId,
+        Has<EdgeDepthPrefilterPipelineIds>,
+    )>,
+) {
+    for (entity, pipeline_id, has_pipelines) in &query {
+        let layout_key = pipeline_id.1;
+        if !layout_key.depth_prefilter {
+            if has_pipelines {
+                commands
+                    .entity(entity)
+                    .remove::<EdgeDepthPrefilterPipelineIds>();
+            }
+            continue;
+        }
+        let linearize = pipelines.specialize(
+            &pipeline_cache,
+            &prefilter_pipeline,
+            EdgeDepthPrefilterKey::Linearize {
+                multisampled: layout_key.multisampled,
+            },
+        );
+        let downsample = pipelines.specialize(
+            &pipeline_cache,
+            &prefilter_pipeline,
+            EdgeDepthPrefilterKey::Downsample,
+        );
+        commands
+            .entity(entity)
+            .insert(EdgeDepthPrefilterPipelineIds {
+                linearize,
+                downsample,
+                multisampled: layout_key.multisampled,
+            });
+    }
+}
+
+/// Allocates a [`ViewEdgeDepthPyramid`] with `depth_prefilter + 1` levels for views
+/// keyed with `depth_prefilter`, and drops it from the others.
+fn prepare_edge_depth_pyramids(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(
+        Entity,
+        &EdgeDetection,
+        &EdgeDetectionPipelineId,
+        &ViewTarget,
+        Has<ViewEdgeDepthPyramid>,
+    )>,
+) {
+    for (entity, edge_detection, pipeline_id, view_target, has_pyramid) in &query {
+        if !pipeline_id.1.depth_prefilter {
+            if has_pyramid {
+                commands.entity(entity).remove::<ViewEdgeDepthPyramid>();
+            }
+            continue;
+        }
+
+        let size = view_target.main_texture().size();
+        let mip_level_count = prefilter_levels(edge_detection) + 1;
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("edge_detection: depth pyramid texture"),
+                size: Extent3d {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: EDGE_DEPTH_PYRAMID_TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        let mips = (0..mip_level_count)
+            .map(|level| {
+                texture.texture.create_view(&TextureViewDescriptor {
+                    label: Some("edge_detection: depth pyramid mip"),
+                    base_mip_level: level,
+                    mip_level_count: Some(1),
+                    ..default()
+                })
+            })
+            .collect();
+        commands
+            .entity(entity)
+            .insert(ViewEdgeDepthPyramid { texture, mips });
+    }
+}
+
+/// Fills `pyramid` from the `depth` prepass: the linearize pipeline into level 0,
+/// then the downsample pipeline into each further level. Returns `false`, encoding
+/// nothing, while the pipelines aren't compiled yet.
+pub(crate) fn build_depth_pyramid(
+    ctx: &mut RenderContext,
+    pipeline_cache: &PipelineCache,
+    prefilter_pipeline: &EdgeDepthPrefilterPipeline,
+    (pyramid, pipeline_ids): (&ViewEdgeDepthPyramid, &EdgeDepthPrefilterPipelineIds),
+    depth: &TextureView,
+    view_uniforms: BindingResource,
+    view_uniform_offset: u32,
+) -> bool {
+    let (Some(linearize), Some(downsample)) = (
+        pipeline_cache.get_render_pipeline(pipeline_ids.linearize),
+        pipeline_cache.get_render_pipeline(pipeline_ids.downsample),
+    ) else {
+        return false;
+    };
+
+    let linearize_bind_group = ctx.render_device().create_bind_group(
+        "edge_detection_depth_linearize_bind_group",
+        &pipeline_cache
+            .get_bind_group_layout(prefilter_pipeline.linearize_layout(pipeline_ids.multisampled)),
+        &BindGroupEntries::sequential((depth, view_uniforms)),
+    );
+    let downsample_layout =
+        pipeline_cache.get_bind_group_layout(&prefilter_pipeline.downsample_layout);
+
+    for (level, mip) in pyramid.mips.iter().enumerate() {
+        let bind_group = (level > 0).then(|| {
+            ctx.render_device().create_bind_group(
+                "edge_detection_depth_downsample_bind_group",
+                &downsample_layout,
+                &BindGroupEntries::single(&pyramid.mips[level - 1]),
+            )
+        });
+
+        let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("edge_detection_depth_prefilter_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: mip,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        match &bind_group {
+            None => {
+                render_pass.set_render_pipeline(linearize);
+                render_pass.set_bind_group(0, &linearize_bind_group, &[view_uniform_offset]);
+            }
+            Some(bind_group) => {
+                render_pass.set_render_pipeline(downsample);
+                render_pass.set_bind_group(0, bind_group, &[]);
+            }
+        }
+        render_pass.draw(0..3, 0..1);
+    }
+    true
+}
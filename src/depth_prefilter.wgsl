@@ -0,0 +1,52 @@
+//! Linear depth mip chain for depth edges (`EdgeDetection::depth_prefilter`).
+//!
+//! LINEARIZE: view z of the depth prepass (its first sample with MULTISAMPLED)
+//! into level 0. Otherwise: 2x2 average of the previous level. The background
+//! (depth 0) is stored as 0 and left out of the averages.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import bevy_render::view::View
+
+#ifdef LINEARIZE
+
+#ifdef MULTISAMPLED
+@group(0) @binding(0) var depth_prepass_texture: texture_depth_multisampled_2d;
+#else
+@group(0) @binding(0) var depth_prepass_texture: texture_depth_2d;
+#endif
+@group(0) @binding(1) var<uniform> view: View;
+
+@fragment
+fn linearize(in: FullscreenVertexOutput) -> @location(0) vec4f {
+    let depth = textureLoad(depth_prepass_texture, vec2i(in.position.xy), 0);
+    if (depth <= 0.0) {
+        return vec4f(0.0);
+    }
+    // Valid for any projection.
+    let view_pos = view.view_from_clip * vec4f(0.0, 0.0, depth, 1.0);
+    return vec4f(view_pos.z / view_pos.w, 0.0, 0.0, 1.0);
+}
+
+#else
+
+@group(0) @binding(0) var previous_level: texture_2d<f32>;
+
+@fragment
+fn downsample(in: FullscreenVertexOutput) -> @location(0) vec4f {
+    let base = vec2i(in.position.xy) * 2;
+    let last = vec2i(textureDimensions(previous_level)) - 1;
+    var sum = 0.0;
+    var count = 0.0;
+    for (var y = 0; y < 2; y++) {
+        for (var x = 0; x < 2; x++) {
+            let z = textureLoad(previous_level, min(base + vec2i(x, y), last), 0).r;
+            if (z != 0.0) {
+                sum += z;
+                count += 1.0;
+            }
+        }
+    }
+    return vec4f(select(0.0, sum / count, count > 0.0), 0.0, 0.0, 1.0);
+}
+
+#endif
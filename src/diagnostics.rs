@@ -0,0 +1,96 @@
+//! Diagnostics for the edge detection pass.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    platform::collections::HashSet,
+    prelude::*,
+    render::RenderApp,
+};
+
+use crate::EdgeDetectionKey;
+
+/// Number of distinct [`EdgeDetectionKey`]s specialized so far. Only measured in
+/// debug builds.
+pub const EDGE_DETECTION_PIPELINE_VARIANTS: DiagnosticPath =
+    DiagnosticPath::const_new("edge_detection/pipeline_variants");
+
+/// GPU time of the edge pass in milliseconds, recorded with the `gpu_timing`
+/// feature when `RenderDiagnosticsPlugin` is added. Devices without timestamp
+/// query support report no GPU measurements for this path.
+pub const EDGE_DETECTION_GPU_TIME: DiagnosticPath =
+    DiagnosticPath::const_new("render/edge_detection/elapsed_gpu");
+
+/// Variant count above which a warning is logged (once). Every keyed feature
+/// multiplies the number of possible variants, and each new variant is a shader
+/// compile (a stutter) the first time a view uses it.
+pub const PIPELINE_VARIANT_WARN_THRESHOLD: usize = 32;
+
+/// Count shared between the render world (writer) and the main world (diagnostic).
+#[derive(Resource, Clone, Default)]
+struct PipelineVariantCount(Arc<AtomicUsize>);
+
+/// Render-world record of every [`EdgeDetectionKey`] specialized so far.
+/// Only present in debug builds.
+#[derive(Resource)]
+pub struct EdgeDetectionPipelineVariants {
+    keys: HashSet<EdgeDetectionKey>,
+    count: PipelineVariantCount,
+}
+
+impl EdgeDetectionPipelineVariants {
+    /// Records a specialized key, warning once if the variant count grows past
+    /// [`PIPELINE_VARIANT_WARN_THRESHOLD`].
+    pub fn record(&mut self, key: EdgeDetectionKey) {
+        if !self.keys.insert(key) {
+            return;
+        }
+        let count = self.keys.len();
+        self.count.0.store(count, Ordering::Relaxed);
+        if count > PIPELINE_VARIANT_WARN_THRESHOLD {
+            warn_once!(
+                "edge detection has specialized {count} pipeline variants (> {PIPELINE_VARIANT_WARN_THRESHOLD}); \
+                 a keyed setting may be changing every frame or the key space has blown up"
+            );
+        }
+    }
+
+    /// Number of distinct keys recorded.
+    pub fn count(&self) -> usize {
+        self.keys.len()
+    }
+}
+
+pub(crate) struct EdgeDetectionDiagnosticsPlugin;
+
+impl Plugin for EdgeDetectionDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        // Variant counting is a maintainer guard; keep it out of release builds.
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        let count = PipelineVariantCount::default();
+        app.insert_resource(count.clone())
+            .register_diagnostic(Diagnostic::new(EDGE_DETECTION_PIPELINE_VARIANTS))
+            .add_systems(Update, measure_pipeline_variants);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.insert_resource(EdgeDetectionPipelineVariants {
+            keys: HashSet::default(),
+            count,
+        });
+    }
+}
+
+fn measure_pipeline_variants(mut diagnostics: Diagnostics, count: Res<PipelineVariantCount>) {
+    diagnostics.add_measurement(&EDGE_DETECTION_PIPELINE_VARIANTS, || {
+        count.0.load(Ordering::Relaxed) as f64
+    });
+}
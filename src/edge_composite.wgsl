@@ -0,0 +1,73 @@
+//! Composites the edge mask (`EdgeDetection::resolution_scale`, `edge_dilation`)
+//! over the full-resolution scene, with the same straight-alpha "over" as the
+//! single-pass output.
+//!
+//! DILATION_RADIUS: take the strongest mask sample within this many view pixels.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var edge_mask_texture: texture_2d<f32>;
+@group(0) @binding(2) var linear_sampler: sampler;
+
+fn ldr_clamp4(c: vec4f) -> vec4f {
+#ifdef FORCE_LDR
+    return clamp(c, vec4f(0.0), vec4f(1.0));
+#else
+    return c;
+#endif
+}
+
+#ifdef DILATION_RADIUS
+/// Strongest edge (by coverage) within a disk of DILATION_RADIUS view pixels, with
+/// that sample's color.
+fn dilated_edge(uv: vec2f) -> vec4f {
+    let radius = i32(#{DILATION_RADIUS});
+    let pixel = 1.0 / vec2f(textureDimensions(screen_texture));
+    let max_dist_sq = (f32(radius) + 0.5) * (f32(radius) + 0.5);
+    var edge = vec4f(0.0);
+    for (var y = -radius; y <= radius; y++) {
+        for (var x = -radius; x <= radius; x++) {
+            if (f32(x * x + y * y) > max_dist_sq) {
+                continue;
+            }
+            let offset = vec2f(f32(x), f32(y)) * pixel;
+            let s = textureSampleLevel(edge_mask_texture, linear_sampler, uv + offset, 0.0);
+            if (s.a > edge.a) {
+                edge = s;
+            }
+        }
+    }
+    return edge;
+}
+#endif
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4f {
+#ifdef DILATION_RADIUS
+    let edge = dilated_edge(in.uv);
+#else
+    // Edge color with coverage in alpha, bilinearly upsampled.
+    let edge = textureSample(edge_mask_texture, linear_sampler, in.uv);
+#endif
+#ifdef TRANSPARENT_BACKGROUND
+    return edge;
+#else
+    let src = ldr_clamp4(textureSample(screen_texture, linear_sampler, in.uv));
+#ifdef EDGE_BLEND_ADDITIVE
+    let out_alpha = src.a;
+    let over = src.rgb + edge.rgb * edge.a;
+#else
+    let out_alpha = edge.a + src.a * (1.0 - edge.a);
+    let over = (edge.rgb * edge.a + src.rgb * src.a * (1.0 - edge.a)) / max(out_alpha, 1e-6);
+#endif
+#ifdef EDGE_BLEND_ALPHA
+    let edge_out = ldr_clamp4(vec4f(over, src.a));
+#else
+    let edge_out = ldr_clamp4(vec4f(over, out_alpha));
+#endif
+    // No-edge pixels pass the source through bit-exact, or clamped under FORCE_LDR
+    // (see the edge shader).
+    return select(src, edge_out, edge.a > 0.0);
+#endif
+}
@@ -1,29 +1,99 @@
 //! Edge Detection Shader
 //!
 //! This shader implements edge detection based on depth, normal, and color gradients.
-//! Three operators are supported via shader defs:
+//! Four operators are supported via shader defs:
 //!   - OPERATOR_SOBEL:        3x3 Sobel filter — 8 samples per type, wider edges, stronger gradients.
 //!   - OPERATOR_ROBERTS_CROSS: 2x2 Roberts Cross — 4 samples per type, clean 1px edges.
+//!   - OPERATOR_ROBERTS_GATHER: Roberts Cross from one textureGather per channel (non-MSAA only;
+//!                            MSAA views fall back to OPERATOR_ROBERTS_CROSS; depth samples the
+//!                            pyramid 4 times under DEPTH_PREFILTER).
 //!   - OPERATOR_PIXEL_ART:    UDLR 4-direction pairwise comparison — 1px guaranteed, silhouette/crease
 //!                            priority, per-entity channel mask (alpha encoding).
+//! The 3x3 operator's kernel is selected by KERNEL_SCHARR / KERNEL_PREWITT / KERNEL_LAPLACIAN
+//! (none of them = Sobel).
 
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 #import bevy_render::view::View
 #import bevy_pbr::view_transformations::uv_to_ndc
 
+// User-supplied edge source (see `EdgeDetectionCustomSource`). The module must
+// `#define_import_path bevy_edge_detection_outline::custom_edge_source` and define
+//   fn custom_edge_value(uv: vec2f, view_z: f32, normal: vec4f, color: vec4f) -> f32
+#ifdef CUSTOM_EDGE_SOURCE
+#import bevy_edge_detection_outline::custom_edge_source::custom_edge_value
+#endif
+
+// User hooks, enabled through `EdgeDetectionPlugin::shader_defs`. Both import from a
+// module declaring `#define_import_path bevy_edge_detection_outline::user_hooks`:
+//   USER_EDGE_POSTPROCESS: fn user_edge_postprocess(uv: vec2f, edge: f32) -> f32
+//     rewrites the final edge strength, before temporal blending and global_intensity.
+//   USER_EDGE_COLOR: fn user_edge_color(uv: vec2f, edge: f32, color: vec4f) -> vec4f
+//     rewrites the edge color, after edge_color_animation.
+#ifdef USER_EDGE_POSTPROCESS
+#import bevy_edge_detection_outline::user_hooks::user_edge_postprocess
+#endif
+#ifdef USER_EDGE_COLOR
+#import bevy_edge_detection_outline::user_hooks::user_edge_color
+#endif
+
+// NO_COLOR_SOURCE: line-art output that never reads the scene color, binding 0 is absent.
+#ifndef NO_COLOR_SOURCE
 @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+#endif
 
+// COLOR_ONLY: no prepass on the view, bindings 1 and 2 are absent.
+// NORMALS_FROM_DEPTH: normals are reconstructed from depth, binding 2 is absent.
+#ifndef COLOR_ONLY
 #ifdef MULTISAMPLED
 @group(0) @binding(1) var depth_prepass_texture: texture_depth_multisampled_2d;
 #else
 @group(0) @binding(1) var depth_prepass_texture: texture_depth_2d;
 #endif
 
+#ifndef NORMALS_FROM_DEPTH
 #ifdef MULTISAMPLED
 @group(0) @binding(2) var normal_prepass_texture: texture_multisampled_2d<f32>;
 #else
 @group(0) @binding(2) var normal_prepass_texture: texture_2d<f32>;
 #endif
+#endif  // NORMALS_FROM_DEPTH
+#endif  // COLOR_ONLY
+
+// MOTION_VECTORS: motion vector prepass (MotionVectorPrepass), for MOTION_GATE
+// and TEMPORAL_HISTORY.
+#ifdef MOTION_VECTORS
+#ifdef MULTISAMPLED
+@group(0) @binding(10) var motion_vector_prepass_texture: texture_multisampled_2d<f32>;
+#else
+@group(0) @binding(10) var motion_vector_prepass_texture: texture_2d<f32>;
+#endif
+#endif
+
+// EDGE_COLOR_FROM_NORMAL: matcap-style color lookup indexed by the view-space normal.
+#ifdef EDGE_COLOR_FROM_NORMAL
+@group(0) @binding(9) var normal_color_texture: texture_2d<f32>;
+#endif
+
+// REGION_MASK: edges are scaled by the red channel (EdgeDetectionRegionMask).
+#ifdef REGION_MASK
+@group(0) @binding(11) var region_mask_texture: texture_2d<f32>;
+#endif
+
+// TEMPORAL_HISTORY: last frame's edge strength (ViewEdgeHistoryTextures), R.
+#ifdef TEMPORAL_HISTORY
+@group(0) @binding(12) var edge_history_texture: texture_2d<f32>;
+#endif
+
+// EDGE_IMPORTANCE: thresholds are divided by the red channel (EdgeDetectionImportance).
+#ifdef EDGE_IMPORTANCE
+@group(0) @binding(13) var importance_texture: texture_2d<f32>;
+#endif
+
+// DEPTH_PREFILTER: downsampled linear view z for depth edges (ViewEdgeDepthPyramid),
+// R, 0 for the background.
+#ifdef DEPTH_PREFILTER
+@group(0) @binding(14) var depth_pyramid_texture: texture_2d<f32>;
+#endif
 
 @group(0) @binding(3) var filtering_sampler: sampler;
 @group(0) @binding(4) var depth_sampler: sampler;
@@ -56,6 +126,90 @@ struct EdgeDetectionUniform {
 
     block_pixel: u32,
     flat_rejection_threshold: f32,
+
+    time: f32,
+    // 0 = none, 1 = pulse, 2 = hue
+    edge_color_anim_mode: u32,
+    edge_color_anim_speed: f32,
+    edge_color_anim_alt: vec4f,
+
+    // 2^bits - 1 per channel; 0 = no quantization
+    output_levels: vec3f,
+
+    plane_threshold: f32,
+
+    distortion_phase: f32,
+
+    custom_threshold: f32,
+    custom_thickness: f32,
+
+    // 1 = pick black/white edges from local background luminance
+    auto_contrast: u32,
+
+    // FLAT_BACKGROUND fill color
+    flat_background: vec4f,
+
+    // radians
+    distortion_rotation: f32,
+
+    threshold_vignette: f32,
+    vignette_radius: f32,
+
+    // depth_threshold ramps to depth_threshold_far over view distance depth_ramp.x..y
+    depth_threshold_far: f32,
+    depth_ramp: vec2f,
+
+    // MOTION_GATE: minimum screen velocity in pixels per frame
+    motion_threshold: f32,
+
+    // [0, 1], scales the final edge strength
+    global_intensity: f32,
+
+    // color-buffer edges (silhouette_color / crease_color cover depth / normal)
+    color_edge_color: vec4f,
+
+    // [0, 1], opacity of the edges over the source
+    edge_opacity: f32,
+
+    // edges fade out between these view distances; disabled when end <= start
+    edge_fade_start: f32,
+    edge_fade_end: f32,
+
+    // noise tiles per second the distortion lookup scrolls by
+    uv_distortion_speed: vec2f,
+
+    // normal gradients are scaled by 1 + rim_strength * (1 - |N.V|)
+    rim_strength: f32,
+
+    // [0, 1], half-width of the threshold ramp relative to the threshold; 0 = hard step
+    edge_smoothness: f32,
+
+    // TEMPORAL_HISTORY: weight of the reprojected history, 0 on a view's first frame
+    temporal_blend: f32,
+
+    // 0 = constant thickness; > 0 thinner with view distance, < 0 thicker
+    thickness_depth_falloff: f32,
+
+    // EDGE_COLOR_BY_DIRECTION: color of horizontal lines
+    edge_color_secondary: vec4f,
+
+    // per-axis multiplier of the detector sampling offsets
+    thickness_axis_scale: vec2f,
+
+    // subtracted from the depth gradient before thresholding
+    depth_bias: f32,
+
+    // UV rectangle (min.xy, max.xy) outside of which edges are dropped
+    viewport_rect: vec4f,
+
+    // EDGE_EMISSIVE: multiplier of the edge color on HDR targets
+    edge_emissive_strength: f32,
+
+    // ENABLE_CREASE: relative depth curvature threshold
+    crease_threshold: f32,
+
+    // UNJITTER_PREPASS: UV offset undoing the TAA jitter of the prepass
+    prepass_jitter: vec2f,
 }
 
 // -----------------------
@@ -64,6 +218,16 @@ struct EdgeDetectionUniform {
 
 fn saturate(x: f32) -> f32 { return clamp(x, 0.0, 1.0); }
 
+/// Edge strength of gradient `grad` against `threshold`: a hard step, or with
+/// `edge_smoothness` a smoothstep over `threshold * (1 +- edge_smoothness)`.
+fn edge_step(threshold: f32, grad: f32) -> f32 {
+    let width = threshold * ed_uniform.edge_smoothness;
+    if (width <= 0.0) {
+        return f32(grad > threshold);
+    }
+    return smoothstep(threshold - width, threshold + width, grad);
+}
+
 /// Retrieve the perspective camera near clipping plane
 fn perspective_camera_near() -> f32 {
     return view.clip_from_view[3][2];
@@ -71,6 +235,8 @@ fn perspective_camera_near() -> f32 {
 
 /// Convert ndc depth to linear view z. 
 /// Note: Depth values in front of the camera will be negative as -z is forward
+/// Custom projections (neither VIEW_PROJECTION_* def) go through the view's
+/// inverse projection, which is valid for any projection matrix.
 fn depth_ndc_to_view_z(ndc_depth: f32) -> f32 {
 #ifdef VIEW_PROJECTION_PERSPECTIVE
     return -perspective_camera_near() / ndc_depth;
@@ -88,24 +254,88 @@ fn position_ndc_to_world(ndc_pos: vec3<f32>) -> vec3<f32> {
     return world_pos.xyz / world_pos.w;
 }
 
+/// UV offset of the detector kernels' neighbor samples for `thickness` texels,
+/// stretched per axis by `thickness_axis_scale`.
+fn thickness_offset(thickness: f32) -> vec2f {
+    return texel_size * thickness * ed_uniform.thickness_axis_scale;
+}
+
+/// BORDER_CLAMP (BorderMode::Suppress/Draw): keep neighbor reads inside the screen so
+/// off-screen samples (e.g. out-of-range MSAA loads) can't produce spurious border edges.
+fn border_clamp_uv(uv: vec2f) -> vec2f {
+#ifdef BORDER_CLAMP
+    return clamp(uv, 0.5 * texel_size, 1.0 - 0.5 * texel_size);
+#else
+    return uv;
+#endif
+}
+
+/// `border_clamp_uv` for a textureGather at `uv`: keeps the whole 2x2 quad around
+/// the shared corner on screen.
+fn border_clamp_quad_uv(uv: vec2f) -> vec2f {
+#ifdef BORDER_CLAMP
+    return clamp(uv, texel_size, 1.0 - texel_size);
+#else
+    return uv;
+#endif
+}
+
+/// UNJITTER_PREPASS: `uv` shifted by this frame's TAA jitter (`prepass_jitter`),
+/// so prepass reads line up with the resolved, unjittered scene color instead of
+/// moving by a fraction of a texel every frame, which makes edges crawl.
+fn unjittered_prepass_uv(uv: vec2f) -> vec2f {
+#ifdef UNJITTER_PREPASS
+    return uv + ed_uniform.prepass_jitter;
+#else
+    return uv;
+#endif
+}
+
 fn calculate_view(world_position: vec3f) -> vec3f {
 #ifdef VIEW_PROJECTION_ORTHOGRAPHIC
         // Orthographic view vector
         return normalize(vec3f(view.clip_from_world[0].z, view.clip_from_world[1].z, view.clip_from_world[2].z));
-#else
+#else ifdef VIEW_PROJECTION_PERSPECTIVE
         // Only valid for a perspective projection
         return normalize(view.world_position.xyz - world_position.xyz);
+#else
+        // Custom projection: unproject a second point further along this pixel's
+        // ray and point back toward the camera.
+        let clip = view.clip_from_world * vec4f(world_position, 1.0);
+        let ndc = clip.xyz / clip.w;
+        let far_world = position_ndc_to_world(vec3f(ndc.xy, ndc.z * 0.5));
+        return normalize(world_position - far_world);
 #endif
 }
 
+#ifndef COLOR_ONLY
+
 // -----------------------
 // Depth Detection -------
 // -----------------------
 
-fn prepass_depth(uv: vec2f) -> f32 {
+#ifdef MULTISAMPLED
+/// One texel of the multisampled depth prepass: the mean of its samples with
+/// DEPTH_RESOLVE_AVERAGE, so edges don't depend on the sample count.
+fn load_depth(pixel_coord: vec2i) -> f32 {
+#ifdef DEPTH_RESOLVE_AVERAGE
+    let count = textureNumSamples(depth_prepass_texture);
+    var sum = 0.0;
+    for (var i = 0u; i < count; i++) {
+        sum += textureLoad(depth_prepass_texture, pixel_coord, i32(i));
+    }
+    return sum / f32(count);
+#else
+    return textureLoad(depth_prepass_texture, pixel_coord, sample_index_i);
+#endif
+}
+#endif
+
+fn prepass_depth(uv_in: vec2f) -> f32 {
+    let uv = border_clamp_uv(uv_in);
 #ifdef MULTISAMPLED
     let pixel_coord = vec2i(uv * texture_size);
-    let depth = textureLoad(depth_prepass_texture, pixel_coord, sample_index_i);
+    let depth = load_depth(pixel_coord);
 #else
     let depth = textureSample(depth_prepass_texture, depth_sampler, uv);
 #endif
@@ -117,30 +347,282 @@ fn prepass_view_z(uv: vec2f) -> f32 {
     return depth_ndc_to_view_z(depth);
 }
 
-fn detect_edge_depth(uv: vec2f, thickness: f32, fresnel: f32) -> f32 {
-    let offset = texel_size * thickness;
+#ifdef ENABLE_CREASE
+/// ENABLE_CREASE: crease strength from the second derivative of the prepass depth.
+/// NDC depth is linear in screen space across any plane (under both projections),
+/// so it only bends where the surface does: flat surfaces give 0 however they are
+/// tilted, folds give a peak. The second difference is taken per UV step and
+/// relative to the center depth, so the threshold holds at any distance,
+/// resolution and thickness.
+fn detect_edge_crease(uv_in: vec2f, thickness: f32) -> f32 {
+    let uv = unjittered_prepass_uv(uv_in);
+    let offset = thickness_offset(thickness);
+    let d_c = prepass_depth(uv);
+    let d_l = prepass_depth(uv - vec2f(offset.x, 0.0));
+    let d_r = prepass_depth(uv + vec2f(offset.x, 0.0));
+    let d_t = prepass_depth(uv - vec2f(0.0, offset.y));
+    let d_b = prepass_depth(uv + vec2f(0.0, offset.y));
+    // Next to the background this is a silhouette, left to depth edges.
+    if (min(min(d_c, d_l), min(min(d_r, d_t), d_b)) <= 0.0) {
+        return 0.0;
+    }
+    let curvature = max(
+        abs(d_l + d_r - 2.0 * d_c) / offset.x,
+        abs(d_t + d_b - 2.0 * d_c) / offset.y,
+    ) / d_c;
+    return edge_step(ed_uniform.crease_threshold, curvature);
+}
+#endif
+
+#ifdef DEPTH_PREFILTER
+/// Size of a depth pyramid texel, in view texels.
+fn depth_prefilter_scale() -> vec2f {
+    return 1.0 / (texel_size * vec2f(textureDimensions(depth_pyramid_texture)));
+}
+
+/// View z for depth edges, bilinearly interpolated from the depth pyramid
+/// (EdgeDetection::depth_prefilter). Background texels don't contribute; where all
+/// four are background this is the background's view z.
+fn depth_edge_view_z(uv_in: vec2f) -> f32 {
+    let size = vec2f(textureDimensions(depth_pyramid_texture));
+    let pos = border_clamp_uv(uv_in) * size - 0.5;
+    let base = floor(pos);
+    let f = pos - base;
+    let last = vec2i(size) - 1;
+    var sum = 0.0;
+    var weight = 0.0;
+    for (var y = 0; y < 2; y++) {
+        for (var x = 0; x < 2; x++) {
+            let coord = clamp(vec2i(base) + vec2i(x, y), vec2i(0), last);
+            let z = textureLoad(depth_pyramid_texture, coord, 0).r;
+            let w = select(1.0 - f.x, f.x, x == 1) * select(1.0 - f.y, f.y, y == 1);
+            if (z != 0.0) {
+                sum += w * z;
+                weight += w;
+            }
+        }
+    }
+    if (weight <= 0.0) {
+        return depth_ndc_to_view_z(0.0);
+    }
+    return sum / weight;
+}
+#else
+/// View z for depth edges.
+fn depth_edge_view_z(uv: vec2f) -> f32 {
+    return prepass_view_z(uv);
+}
+#endif
+
+/// Steep-angle compensation for a pixel at view distance `view_dist` with the given
+/// `fresnel` (1 - N.V): the depth threshold is multiplied by `1 + result`.
+///
+/// The depth step between neighboring texels on a tilted plane is
+/// `texel footprint * tan(angle)`. Under perspective the footprint grows linearly
+/// with distance, hence the `view_dist` factor. Under an orthographic projection the
+/// footprint is constant, so the step doesn't depend on distance: scaling by
+/// `view_dist` there over-compensates far away and under-compensates up close
+/// (doubled outlines on tilted ground), so the ortho branch drops that factor.
+fn steep_angle_adjustment(fresnel: f32, view_dist: f32) -> f32 {
+    let steepness = smoothstep(ed_uniform.steep_angle_threshold, 1.0, fresnel) * ed_uniform.steep_angle_multiplier;
+#ifdef VIEW_PROJECTION_ORTHOGRAPHIC
+    return steepness;
+#else
+    return steepness * view_dist;
+#endif
+}
 
+/// Depth threshold at view distance `view_dist`: `depth_threshold` up to
+/// `depth_ramp.x`, `depth_threshold_far` from `depth_ramp.y`, linear in between.
+/// An empty ramp (y <= x) keeps `depth_threshold` everywhere.
+fn depth_threshold_at(view_dist: f32) -> f32 {
+    let span = ed_uniform.depth_ramp.y - ed_uniform.depth_ramp.x;
+    let t = select(0.0, saturate((view_dist - ed_uniform.depth_ramp.x) / span), span > 0.0);
+    return mix(ed_uniform.depth_threshold, ed_uniform.depth_threshold_far, t) * threshold_scale;
+}
+
+#ifdef SILHOUETTE_HALO
+/// Halo around the silhouette of everything in the depth prepass (with a masked
+/// prepass: the selected objects). Only pixels outside the mask get the halo, so it
+/// expands outward; it fades from 1 at the silhouette to 0 at `radius` texels.
+fn silhouette_halo(uv: vec2f, radius: f32) -> f32 {
+    let r = max(radius, 1.0);
+    let steps = i32(ceil(r));
+    var nearest = r + 1.0;
+    for (var y = -steps; y <= steps; y++) {
+        for (var x = -steps; x <= steps; x++) {
+            let d = length(vec2f(f32(x), f32(y)));
+            // Reverse-Z: cleared (empty) depth is 0.
+            let inside = prepass_depth(uv + vec2f(f32(x), f32(y)) * texel_size) > 0.0;
+            nearest = select(nearest, min(nearest, d), inside && d <= r);
+        }
+    }
+    let outside = prepass_depth(uv) <= 0.0;
+    let halo = saturate(1.0 - (nearest - 1.0) / r);
+    return select(0.0, halo, outside && nearest <= r);
+}
+#endif
+
+#ifdef OUTLINE_AGAINST_BACKGROUND
+/// 1 where the pixel and one of its 4 neighbors `thickness` texels away disagree on
+/// whether there is geometry, else 0. Reverse-Z: cleared (empty) depth is 0.
+fn background_edge(uv_in: vec2f, thickness: f32) -> f32 {
+    let uv = unjittered_prepass_uv(uv_in);
+    let offset = thickness_offset(max(thickness, 1.0));
+    let center = prepass_depth(uv) > 0.0;
+    let left = prepass_depth(uv - vec2f(offset.x, 0.0)) > 0.0;
+    let right = prepass_depth(uv + vec2f(offset.x, 0.0)) > 0.0;
+    let up = prepass_depth(uv - vec2f(0.0, offset.y)) > 0.0;
+    let down = prepass_depth(uv + vec2f(0.0, offset.y)) > 0.0;
+    let differs = center != left || center != right || center != up || center != down;
+    return select(0.0, 1.0, differs);
+}
+#endif
+
+// -----------------------
+// 3x3 Kernel Weights -----
+// -----------------------
+
+// Gradient kernels as (corner, side) weights, normalized to Sobel's total gain of 4
+// so thresholds carry over between kernels.
+#ifdef KERNEL_SCHARR
+const KERNEL_CORNER: f32 = 0.75;  // 3/16 * 4
+const KERNEL_SIDE: f32 = 2.5;     // 10/16 * 4
+#else ifdef KERNEL_PREWITT
+const KERNEL_CORNER: f32 = 1.3333333;
+const KERNEL_SIDE: f32 = 1.3333333;
+#else
+const KERNEL_CORNER: f32 = 1.0;
+const KERNEL_SIDE: f32 = 2.0;
+#endif
+
+// The 4-neighbor Laplacian responds with 1x the step height where Sobel gives 4x.
+const LAPLACIAN_GAIN: f32 = 4.0;
+
+#ifdef KERNEL_RADIUS
+// EdgeDetection::kernel_radius > 1: (2r+1)x(2r+1) Sobel-style kernels. Weight of the
+// sample at (x, y) in gx is sign(x) * binomial(y), in gy sign(y) * binomial(x);
+// radius 1 is exactly the 3x3 Sobel kernel.
+const KERNEL_RADIUS: i32 = i32(#{KERNEL_RADIUS});
+
+/// Smoothing weight of row/column `j` in [-KERNEL_RADIUS, KERNEL_RADIUS]: Pascal's
+/// triangle row 2 * KERNEL_RADIUS ([1 4 6 4 1], [1 6 15 20 15 6 1]).
+fn wide_kernel_smoothing(j: i32) -> f32 {
+    let n = 2 * KERNEL_RADIUS;
+    let k = j + KERNEL_RADIUS;
+    var w = 1.0;
+    for (var i = 1; i <= k; i++) {
+        w = w * f32(n - k + i) / f32(i);
+    }
+    return w;
+}
+
+/// Scales the wide kernels to the 3x3 Sobel response of 4 to a unit step.
+fn wide_kernel_gain() -> f32 {
+    let r = f32(KERNEL_RADIUS);
+    return 4.0 / (r * exp2(2.0 * r));
+}
+
+/// Signed sample weights (gx, gy) of offset (x, y).
+fn wide_kernel_weights(x: i32, y: i32) -> vec2f {
+    return vec2f(f32(sign(x)) * wide_kernel_smoothing(y), f32(sign(y)) * wide_kernel_smoothing(x));
+}
+#endif
+
+fn detect_edge_depth(uv_in: vec2f, thickness: f32, fresnel: f32) -> f32 {
+    let uv = unjittered_prepass_uv(uv_in);
+#ifdef DEPTH_PREFILTER
+    // Step over pyramid texels, not view texels, or neighbors read the same value.
+    let offset = thickness_offset(thickness) * depth_prefilter_scale();
+#else
+    let offset = thickness_offset(thickness);
+#endif
+
+#ifdef DEPTH_EDGE_MAX_NEIGHBOR_DELTA
+    // DepthEdgeMode::MaxNeighborDelta: largest view-z difference between the pixel
+    // and any of its 8 neighbors, so a one-texel wire against the background
+    // registers fully instead of cancelling out in a symmetric gradient. Scaled by
+    // the operator's step gain so depth_threshold means the same in both modes.
+    let d_c = depth_edge_view_z(uv);
+    var max_delta = 0.0;
+    for (var y = -1; y <= 1; y++) {
+        for (var x = -1; x <= 1; x++) {
+            if (x == 0 && y == 0) {
+                continue;
+            }
+            let d = depth_edge_view_z(uv + vec2f(f32(x), f32(y)) * offset);
+            max_delta = max(max_delta, abs(d - d_c));
+        }
+    }
 #ifdef OPERATOR_SOBEL
-    // 3x3 Sobel: horizontal/vertical gradient from 8 neighbors
-    let d_tl = prepass_view_z(uv + vec2f(-offset.x,  offset.y));
-    let d_t  = prepass_view_z(uv + vec2f(      0.0,  offset.y));
-    let d_tr = prepass_view_z(uv + vec2f( offset.x,  offset.y));
-    let d_l  = prepass_view_z(uv + vec2f(-offset.x,       0.0));
-    let d_r  = prepass_view_z(uv + vec2f( offset.x,       0.0));
-    let d_bl = prepass_view_z(uv + vec2f(-offset.x, -offset.y));
-    let d_b  = prepass_view_z(uv + vec2f(      0.0, -offset.y));
-    let d_br = prepass_view_z(uv + vec2f( offset.x, -offset.y));
-
-    let gx = -d_tl - 2.0*d_l - d_bl + d_tr + 2.0*d_r + d_br;
-    let gy = -d_tl - 2.0*d_t - d_tr + d_bl + 2.0*d_b + d_br;
+    let grad = 4.0 * max_delta;
+#else
+    let grad = max_delta;
+#endif
+    let view_z = abs(d_c);
+#else ifdef OPERATOR_SOBEL
+#ifdef KERNEL_LAPLACIAN
+    // 4-neighbor Laplacian: second derivative from 5 samples
+    let d_c = depth_edge_view_z(uv);
+    let d_t = depth_edge_view_z(uv + vec2f(      0.0,  offset.y));
+    let d_l = depth_edge_view_z(uv + vec2f(-offset.x,       0.0));
+    let d_r = depth_edge_view_z(uv + vec2f( offset.x,       0.0));
+    let d_b = depth_edge_view_z(uv + vec2f(      0.0, -offset.y));
+
+    let grad = LAPLACIAN_GAIN * abs(d_t + d_l + d_r + d_b - 4.0*d_c);
+    let view_z = abs(d_c);
+#else ifdef KERNEL_RADIUS
+    var g = vec2f(0.0);
+    for (var y = -KERNEL_RADIUS; y <= KERNEL_RADIUS; y++) {
+        for (var x = -KERNEL_RADIUS; x <= KERNEL_RADIUS; x++) {
+            if (x == 0 && y == 0) {
+                continue;
+            }
+            g += wide_kernel_weights(x, y) * depth_edge_view_z(uv + vec2f(f32(x), f32(y)) * offset);
+        }
+    }
+    g *= wide_kernel_gain();
+    let grad = max(abs(g.x), abs(g.y));
+    let view_z = abs(depth_edge_view_z(uv));
+#else
+    // 3x3 gradient (Sobel/Scharr/Prewitt): horizontal/vertical gradient from 8 neighbors
+    let d_tl = depth_edge_view_z(uv + vec2f(-offset.x,  offset.y));
+    let d_t  = depth_edge_view_z(uv + vec2f(      0.0,  offset.y));
+    let d_tr = depth_edge_view_z(uv + vec2f( offset.x,  offset.y));
+    let d_l  = depth_edge_view_z(uv + vec2f(-offset.x,       0.0));
+    let d_r  = depth_edge_view_z(uv + vec2f( offset.x,       0.0));
+    let d_bl = depth_edge_view_z(uv + vec2f(-offset.x, -offset.y));
+    let d_b  = depth_edge_view_z(uv + vec2f(      0.0, -offset.y));
+    let d_br = depth_edge_view_z(uv + vec2f( offset.x, -offset.y));
+
+    let gx = KERNEL_CORNER*(-d_tl - d_bl + d_tr + d_br) + KERNEL_SIDE*(d_r - d_l);
+    let gy = KERNEL_CORNER*(-d_tl - d_tr + d_bl + d_br) + KERNEL_SIDE*(d_b - d_t);
     let grad = max(abs(gx), abs(gy));
-    let view_z = abs(prepass_view_z(uv));
+    let view_z = abs(depth_edge_view_z(uv));
+#endif
+#else ifdef OPERATOR_ROBERTS_GATHER
+#ifdef DEPTH_PREFILTER
+    // The depth pyramid is filtered on load, not gathered: read its quad with four
+    // samples like OPERATOR_ROBERTS_CROSS.
+    let d00 = depth_edge_view_z(uv);
+    let d10 = depth_edge_view_z(uv + vec2f(offset.x, 0.0));
+    let d01 = depth_edge_view_z(uv + vec2f(0.0, offset.y));
+    let d11 = depth_edge_view_z(uv + offset);
+    let grad = max(abs(d00 - d11), abs(d10 - d01));
+    let view_z = abs(d00);
+#else
+    // 2x2 Roberts Cross from a single gather of the pixel's quad (thickness is fixed to 1 texel).
+    // Gather order: x = (0,1), y = (1,1), z = (1,0), w = (0,0).
+    let q = gather_view_z(uv + 0.5 * texel_size);
+    let grad = max(abs(q.w - q.y), abs(q.z - q.x));
+    let view_z = abs(q.w);
+#endif
 #else
     // 2x2 Roberts Cross: diagonal differences from 4 samples
-    let d00 = prepass_view_z(uv);
-    let d10 = prepass_view_z(uv + vec2f(offset.x, 0.0));
-    let d01 = prepass_view_z(uv + vec2f(0.0, offset.y));
-    let d11 = prepass_view_z(uv + offset);
+    let d00 = depth_edge_view_z(uv);
+    let d10 = depth_edge_view_z(uv + vec2f(offset.x, 0.0));
+    let d01 = depth_edge_view_z(uv + vec2f(0.0, offset.y));
+    let d11 = depth_edge_view_z(uv + offset);
 
     let diff_diag0 = d00 - d11;
     let diff_diag1 = d10 - d01;
@@ -148,16 +630,80 @@ fn detect_edge_depth(uv: vec2f, thickness: f32, fresnel: f32) -> f32 {
     let view_z = abs(d00);
 #endif
 
-    let steep_angle_adjustment =
-        smoothstep(ed_uniform.steep_angle_threshold, 1.0, fresnel) * ed_uniform.steep_angle_multiplier * view_z;
+#ifdef THIN_FEATURE_RECOVERY
+    let grad_thin = max(grad, thin_feature_depth_spread(uv));
+#else
+    let grad_thin = grad;
+#endif
+    let grad_biased = max(grad_thin - ed_uniform.depth_bias, 0.0);
+
+    let steep_adj = steep_angle_adjustment(fresnel, view_z);
+
+    return edge_step(depth_threshold_at(view_z) * (1.0 + steep_adj), grad_biased);
+}
+
+/// View z of the 2x2 texel quad whose shared corner is nearest `uv`.
+fn gather_view_z(uv_in: vec2f) -> vec4f {
+    let uv = border_clamp_quad_uv(uv_in);
+#ifdef MULTISAMPLED
+    // textureGather doesn't support multisampled textures: load the quad instead.
+    let p = vec2i(floor(uv * texture_size - 0.5));
+    let d = vec4f(
+        load_depth(p + vec2i(0, 1)),
+        load_depth(p + vec2i(1, 1)),
+        load_depth(p + vec2i(1, 0)),
+        load_depth(p),
+    );
+#else
+    // One fetch returns all four texels of the quad.
+    let d = textureGather(depth_prepass_texture, depth_sampler, uv);
+#endif
+    return vec4f(
+        depth_ndc_to_view_z(d.x),
+        depth_ndc_to_view_z(d.y),
+        depth_ndc_to_view_z(d.z),
+        depth_ndc_to_view_z(d.w),
+    );
+}
 
-    return f32(grad > ed_uniform.depth_threshold * (1.0 + steep_angle_adjustment));
+#ifdef THIN_FEATURE_RECOVERY
+/// Max view-z spread over the two quads diagonally around `uv`. Catches features
+/// too thin to straddle the operator's sample offsets.
+fn thin_feature_depth_spread(uv: vec2f) -> f32 {
+    let a = gather_view_z(uv + 0.5 * texel_size);
+    let b = gather_view_z(uv - 0.5 * texel_size);
+    let hi = max(max(max(a.x, a.y), max(a.z, a.w)), max(max(b.x, b.y), max(b.z, b.w)));
+    let lo = min(min(min(a.x, a.y), min(a.z, a.w)), min(min(b.x, b.y), min(b.z, b.w)));
+    return hi - lo;
 }
+#endif
 
 // -----------------------
 // Normal Detection ------
 // -----------------------
 
+/// Normal prepass texel converted to the `n * 0.5 + 0.5` packing every reader
+/// expects, alpha (the per-entity edge mask) kept as is.
+/// NORMAL_ENCODING_OCTAHEDRAL: RG holds the octahedral-encoded normal; RG = 0 is a
+/// cleared pixel and stays 0.
+fn decode_normal_sample(packed: vec4f) -> vec4f {
+#ifdef NORMAL_ENCODING_OCTAHEDRAL
+    if (all(packed.xy == vec2f(0.0))) {
+        return vec4f(vec3f(0.0), packed.a);
+    }
+    let f = packed.xy * 2.0 - 1.0;
+    var n = vec3f(f, 1.0 - abs(f.x) - abs(f.y));
+    // Lower hemisphere: unfold the corners.
+    let t = max(-n.z, 0.0);
+    n.x += select(t, -t, n.x >= 0.0);
+    n.y += select(t, -t, n.y >= 0.0);
+    return vec4f(normalize(n) * 0.5 + 0.5, packed.a);
+#else
+    return packed;
+#endif
+}
+
+/// World-space normal at `uv`.
 fn prepass_normal_unpack(uv: vec2f) -> vec3f {
     let normal_packed = prepass_normal(uv);
     return normalize(normal_packed.xyz * 2.0 - vec3f(1.0));
@@ -167,20 +713,144 @@ fn prepass_normal(uv: vec2f) -> vec3f {
     return prepass_normal_raw(uv).xyz;
 }
 
-fn prepass_normal_raw(uv: vec2f) -> vec4f {
-#ifdef MULTISAMPLED
+fn prepass_normal_raw(uv_in: vec2f) -> vec4f {
+    let uv = border_clamp_uv(uv_in);
+#ifdef NORMALS_FROM_DEPTH
+    let normal = reconstruct_normal(uv);
+#else ifdef MULTISAMPLED
     let pixel_coord = vec2i(uv * texture_size);
-    let normal = textureLoad(normal_prepass_texture, pixel_coord, sample_index_i);
+#ifdef NORMAL_RESOLVE_DOMINANT
+    let normal = dominant_normal_sample(pixel_coord);
+#else ifdef NORMAL_RESOLVE_AVERAGE
+    let normal = average_normal_sample(pixel_coord);
 #else
-    let normal = textureSample(normal_prepass_texture, filtering_sampler, uv);
+    let normal = decode_normal_sample(
+        textureLoad(normal_prepass_texture, pixel_coord, sample_index_i)
+    );
+#endif
+#else
+    let normal = decode_normal_sample(textureSample(normal_prepass_texture, filtering_sampler, uv));
 #endif
     return normal;
 }
 
-fn detect_edge_normal(uv: vec2f, thickness: f32) -> f32 {
-    let offset = texel_size * thickness;
+#ifdef NORMALS_FROM_DEPTH
+fn prepass_world_position(uv: vec2f, depth: f32) -> vec3f {
+    return position_ndc_to_world(vec3f(uv_to_ndc(uv), depth));
+}
+
+/// Face normal from the depth prepass, packed like the normal prepass
+/// (`n * 0.5 + 0.5`, alpha 1 = edge_mask::BOTH; empty pixels are 0 like a cleared
+/// prepass). Screen-space derivatives (`dpdx`/`dpdy`) only exist for the current
+/// fragment, and the operators read normals at neighbor offsets, so the tangents
+/// are finite differences of neighbor depths instead. Each axis uses the side with
+/// the smaller depth step, so pixels on a silhouette take the normal of their own
+/// surface rather than a slope across the gap.
+fn reconstruct_normal(uv: vec2f) -> vec4f {
+    let depth = prepass_depth(uv);
+    // Reverse-Z: cleared (empty) depth is 0.
+    if (depth <= 0.0) {
+        return vec4f(0.0);
+    }
+    let d_l = prepass_depth(uv - vec2f(texel_size.x, 0.0));
+    let d_r = prepass_depth(uv + vec2f(texel_size.x, 0.0));
+    let d_t = prepass_depth(uv - vec2f(0.0, texel_size.y));
+    let d_b = prepass_depth(uv + vec2f(0.0, texel_size.y));
+
+    let p = prepass_world_position(uv, depth);
+    let use_right = abs(d_r - depth) < abs(d_l - depth);
+    let use_bottom = abs(d_b - depth) < abs(d_t - depth);
+    let ddx = select(
+        p - prepass_world_position(uv - vec2f(texel_size.x, 0.0), d_l),
+        prepass_world_position(uv + vec2f(texel_size.x, 0.0), d_r) - p,
+        use_right,
+    );
+    let ddy = select(
+        p - prepass_world_position(uv - vec2f(0.0, texel_size.y), d_t),
+        prepass_world_position(uv + vec2f(0.0, texel_size.y), d_b) - p,
+        use_bottom,
+    );
+
+    var n = normalize(cross(ddx, ddy));
+    // Face the camera.
+    n = select(n, -n, dot(n, calculate_view(p)) < 0.0);
+    return vec4f(n * 0.5 + 0.5, 1.0);
+}
+#endif
+
+#ifdef NORMAL_RESOLVE_DOMINANT
+/// MSAA normal resolve for pixels whose samples straddle an edge: averaging them
+/// gives a normal that belongs to neither surface, and a fixed sample index picks
+/// one arbitrarily. Instead every sample is loaded, each votes for the samples
+/// whose normal is within ~25 degrees of its own, and the sample with the most
+/// votes (the surface covering most of the pixel) is returned unaveraged.
+fn dominant_normal_sample(pixel_coord: vec2i) -> vec4f {
+    let count = min(textureNumSamples(normal_prepass_texture), 8u);
+    var samples: array<vec4f, 8>;
+    for (var i = 0u; i < count; i++) {
+        samples[i] = decode_normal_sample(textureLoad(normal_prepass_texture, pixel_coord, i32(i)));
+    }
+    var best = samples[0];
+    var best_votes = 0u;
+    for (var i = 0u; i < count; i++) {
+        let n_i = samples[i].xyz * 2.0 - 1.0;
+        var votes = 0u;
+        for (var j = 0u; j < count; j++) {
+            let n_j = samples[j].xyz * 2.0 - 1.0;
+            votes += u32(dot(n_i, n_j) > 0.9);
+        }
+        if (votes > best_votes) {
+            best_votes = votes;
+            best = samples[i];
+        }
+    }
+    return best;
+}
+#endif
+
+#ifdef NORMAL_RESOLVE_AVERAGE
+/// MSAA normal resolve matching `Msaa::Off`: the mean of every packed sample. The
+/// alpha (per-entity edge mask bits) isn't averaged; it comes from the shaded sample.
+fn average_normal_sample(pixel_coord: vec2i) -> vec4f {
+    let count = textureNumSamples(normal_prepass_texture);
+    var sum = vec3f(0.0);
+    for (var i = 0u; i < count; i++) {
+        sum += decode_normal_sample(textureLoad(normal_prepass_texture, pixel_coord, i32(i))).xyz;
+    }
+    let mask = textureLoad(normal_prepass_texture, pixel_coord, sample_index_i).a;
+    return vec4f(sum / f32(count), mask);
+}
+#endif
+
+fn detect_edge_normal(uv_in: vec2f, thickness: f32) -> f32 {
+    let uv = unjittered_prepass_uv(uv_in);
+    let offset = thickness_offset(thickness);
 
 #ifdef OPERATOR_SOBEL
+#ifdef KERNEL_LAPLACIAN
+    let n_c = prepass_normal(uv);
+    let n_t = prepass_normal(uv + vec2f(      0.0,  offset.y));
+    let n_l = prepass_normal(uv + vec2f(-offset.x,       0.0));
+    let n_r = prepass_normal(uv + vec2f( offset.x,       0.0));
+    let n_b = prepass_normal(uv + vec2f(      0.0, -offset.y));
+
+    let grad = LAPLACIAN_GAIN * length(n_t + n_l + n_r + n_b - 4.0*n_c);
+#else ifdef KERNEL_RADIUS
+    var gx = vec3f(0.0);
+    var gy = vec3f(0.0);
+    for (var y = -KERNEL_RADIUS; y <= KERNEL_RADIUS; y++) {
+        for (var x = -KERNEL_RADIUS; x <= KERNEL_RADIUS; x++) {
+            if (x == 0 && y == 0) {
+                continue;
+            }
+            let w = wide_kernel_weights(x, y);
+            let n = prepass_normal(uv + vec2f(f32(x), f32(y)) * offset);
+            gx += w.x * n;
+            gy += w.y * n;
+        }
+    }
+    let grad = wide_kernel_gain() * sqrt(dot(gx, gx) + dot(gy, gy));
+#else
     let n_tl = prepass_normal(uv + vec2f(-offset.x,  offset.y));
     let n_t  = prepass_normal(uv + vec2f(      0.0,  offset.y));
     let n_tr = prepass_normal(uv + vec2f( offset.x,  offset.y));
@@ -190,9 +860,19 @@ fn detect_edge_normal(uv: vec2f, thickness: f32) -> f32 {
     let n_b  = prepass_normal(uv + vec2f(      0.0, -offset.y));
     let n_br = prepass_normal(uv + vec2f( offset.x, -offset.y));
 
-    let gx = -n_tl - 2.0*n_l - n_bl + n_tr + 2.0*n_r + n_br;
-    let gy = -n_tl - 2.0*n_t - n_tr + n_bl + 2.0*n_b + n_br;
+    let gx = KERNEL_CORNER*(-n_tl - n_bl + n_tr + n_br) + KERNEL_SIDE*(n_r - n_l);
+    let gy = KERNEL_CORNER*(-n_tl - n_tr + n_bl + n_br) + KERNEL_SIDE*(n_b - n_t);
     let grad = sqrt(dot(gx, gx) + dot(gy, gy));
+#endif
+#else ifdef OPERATOR_ROBERTS_GATHER
+    // One gather per channel of the pixel's quad: 3 fetches instead of 4 samples.
+    let q = border_clamp_quad_uv(uv + 0.5 * texel_size);
+    let nx = textureGather(0, normal_prepass_texture, filtering_sampler, q);
+    let ny = textureGather(1, normal_prepass_texture, filtering_sampler, q);
+    let nz = textureGather(2, normal_prepass_texture, filtering_sampler, q);
+    let diff0 = vec3f(nx.w, ny.w, nz.w) - vec3f(nx.y, ny.y, nz.y);
+    let diff1 = vec3f(nx.z, ny.z, nz.z) - vec3f(nx.x, ny.x, nz.x);
+    let grad = sqrt(dot(diff0, diff0) + dot(diff1, diff1));
 #else
     let n00 = prepass_normal(uv);
     let n10 = prepass_normal(uv + vec2f(offset.x, 0.0));
@@ -204,46 +884,227 @@ fn detect_edge_normal(uv: vec2f, thickness: f32) -> f32 {
     let grad = sqrt(dot(diff0, diff0) + dot(diff1, diff1));
 #endif
 
-    return f32(grad > ed_uniform.normal_threshold);
+    return edge_step(ed_uniform.normal_threshold * threshold_scale, grad * rim_boost);
 }
 
+#endif  // COLOR_ONLY
+
+// -----------------------
+// Joint Depth+Normal -----
+// -----------------------
+
+#ifdef JOINT_DEPTH_NORMAL
+/// Plane-fit detector: how far do the neighbors lie off the tangent plane
+/// defined by the center pixel's position and normal?
+/// Returns 0 = no edge, 1 = silhouette (off-plane with a depth jump), 2 = crease.
+fn detect_edge_joint(uv_in: vec2f, thickness: f32) -> u32 {
+    let uv = unjittered_prepass_uv(uv_in);
+    let offset = thickness_offset(thickness);
+    let offsets = array<vec2f, 4>(
+        vec2f(offset.x, 0.0),
+        vec2f(-offset.x, 0.0),
+        vec2f(0.0, offset.y),
+        vec2f(0.0, -offset.y),
+    );
+
+    let d0 = prepass_depth(uv);
+    let p0 = position_ndc_to_world(vec3f(uv_to_ndc(uv), d0));
+    let n0 = prepass_normal_unpack(uv);
+    let z0 = depth_ndc_to_view_z(d0);
+    // World-space size of one sample offset at the center depth, so the
+    // residual is measured in "pixels" and is independent of distance.
+    let footprint = max(length(position_ndc_to_world(vec3f(uv_to_ndc(uv + offsets[0]), d0)) - p0), 1e-6);
+
+    var result = 0u;
+    for (var i = 0; i < 4; i++) {
+        let uv_i = uv + offsets[i];
+        let d_i = prepass_depth(uv_i);
+        let p_i = position_ndc_to_world(vec3f(uv_to_ndc(uv_i), d_i));
+        let residual = abs(dot(p_i - p0, n0)) / footprint;
+        if (residual > ed_uniform.plane_threshold * threshold_scale) {
+            if (abs(depth_ndc_to_view_z(d_i) - z0) > depth_threshold_at(abs(z0))) {
+                return 1u;
+            }
+            result = 2u;
+        }
+    }
+    return result;
+}
+#endif
+
 // ----------------------
 // Color Detection ------
 // ----------------------
 
+#ifndef NO_COLOR_SOURCE
 fn prepass_color(uv: vec2f) -> vec3f {
-    return textureSample(screen_texture, filtering_sampler, uv).rgb;
+    return ldr_clamp4(textureSample(screen_texture, filtering_sampler, border_clamp_uv(uv))).rgb;
+}
+#endif
+
+/// FORCE_LDR: treat the (tonemapped) HDR target as if it were LDR by clamping
+/// everything read from or written to it to [0, 1].
+fn ldr_clamp4(v: vec4f) -> vec4f {
+#ifdef FORCE_LDR
+    return clamp(v, vec4f(0.0), vec4f(1.0));
+#else
+    return v;
+#endif
+}
+
+#ifndef NO_COLOR_SOURCE
+#ifdef COLOR_EDGE_OKLAB
+/// Linear sRGB to Oklab (Björn Ottosson's matrices). The cube root keeps the sign so
+/// negative HDR values don't produce NaNs.
+fn linear_srgb_to_oklab(c: vec3f) -> vec3f {
+    let lms = vec3f(
+        0.4122214708 * c.r + 0.5363325363 * c.g + 0.0514459929 * c.b,
+        0.2119034982 * c.r + 0.6806995451 * c.g + 0.1073969566 * c.b,
+        0.0883024619 * c.r + 0.2817188376 * c.g + 0.6299787005 * c.b,
+    );
+    let l = sign(lms) * pow(abs(lms), vec3f(1.0 / 3.0));
+    return vec3f(
+        0.2104542553 * l.x + 0.7936177850 * l.y - 0.0040720928 * l.z,
+        1.9779984951 * l.x - 2.4285922050 * l.y + 0.4505937099 * l.z,
+        0.0259040371 * l.x + 0.7827717662 * l.y - 0.8086757660 * l.z,
+    );
+}
+#endif
+
+/// A scene color in the space color edges are measured in (ColorEdgeSpace).
+fn color_edge_space(c: vec3f) -> vec3f {
+#ifdef COLOR_EDGE_LUMINANCE
+    return vec3f(luminance(c), 0.0, 0.0);
+#else ifdef COLOR_EDGE_OKLAB
+    return linear_srgb_to_oklab(c);
+#else
+    return c;
+#endif
+}
+
+fn edge_color_sample(uv: vec2f) -> vec3f {
+    return color_edge_space(prepass_color(uv));
 }
 
 fn detect_edge_color(uv: vec2f, thickness: f32) -> f32 {
-    let offset = texel_size * thickness;
+    let offset = thickness_offset(thickness);
 
 #ifdef OPERATOR_SOBEL
-    let c_tl = prepass_color(uv + vec2f(-offset.x,  offset.y));
-    let c_t  = prepass_color(uv + vec2f(      0.0,  offset.y));
-    let c_tr = prepass_color(uv + vec2f( offset.x,  offset.y));
-    let c_l  = prepass_color(uv + vec2f(-offset.x,       0.0));
-    let c_r  = prepass_color(uv + vec2f( offset.x,       0.0));
-    let c_bl = prepass_color(uv + vec2f(-offset.x, -offset.y));
-    let c_b  = prepass_color(uv + vec2f(      0.0, -offset.y));
-    let c_br = prepass_color(uv + vec2f( offset.x, -offset.y));
-
-    let gx = -c_tl - 2.0*c_l - c_bl + c_tr + 2.0*c_r + c_br;
-    let gy = -c_tl - 2.0*c_t - c_tr + c_bl + 2.0*c_b + c_br;
+#ifdef KERNEL_LAPLACIAN
+    let c_c = edge_color_sample(uv);
+    let c_t = edge_color_sample(uv + vec2f(      0.0,  offset.y));
+    let c_l = edge_color_sample(uv + vec2f(-offset.x,       0.0));
+    let c_r = edge_color_sample(uv + vec2f( offset.x,       0.0));
+    let c_b = edge_color_sample(uv + vec2f(      0.0, -offset.y));
+
+    let grad = LAPLACIAN_GAIN * length(c_t + c_l + c_r + c_b - 4.0*c_c);
+#else ifdef KERNEL_RADIUS
+    var gx = vec3f(0.0);
+    var gy = vec3f(0.0);
+    for (var y = -KERNEL_RADIUS; y <= KERNEL_RADIUS; y++) {
+        for (var x = -KERNEL_RADIUS; x <= KERNEL_RADIUS; x++) {
+            if (x == 0 && y == 0) {
+                continue;
+            }
+            let w = wide_kernel_weights(x, y);
+            let c = edge_color_sample(uv + vec2f(f32(x), f32(y)) * offset);
+            gx += w.x * c;
+            gy += w.y * c;
+        }
+    }
+    let grad = wide_kernel_gain() * sqrt(dot(gx, gx) + dot(gy, gy));
+#else
+    let c_tl = edge_color_sample(uv + vec2f(-offset.x,  offset.y));
+    let c_t  = edge_color_sample(uv + vec2f(      0.0,  offset.y));
+    let c_tr = edge_color_sample(uv + vec2f( offset.x,  offset.y));
+    let c_l  = edge_color_sample(uv + vec2f(-offset.x,       0.0));
+    let c_r  = edge_color_sample(uv + vec2f( offset.x,       0.0));
+    let c_bl = edge_color_sample(uv + vec2f(-offset.x, -offset.y));
+    let c_b  = edge_color_sample(uv + vec2f(      0.0, -offset.y));
+    let c_br = edge_color_sample(uv + vec2f( offset.x, -offset.y));
+
+    let gx = KERNEL_CORNER*(-c_tl - c_bl + c_tr + c_br) + KERNEL_SIDE*(c_r - c_l);
+    let gy = KERNEL_CORNER*(-c_tl - c_tr + c_bl + c_br) + KERNEL_SIDE*(c_b - c_t);
     let grad = sqrt(dot(gx, gx) + dot(gy, gy));
+#endif
+#else ifdef OPERATOR_ROBERTS_GATHER
+    let q = border_clamp_quad_uv(uv + 0.5 * texel_size);
+    let cr = ldr_clamp4(textureGather(0, screen_texture, filtering_sampler, q));
+    let cg = ldr_clamp4(textureGather(1, screen_texture, filtering_sampler, q));
+    let cb = ldr_clamp4(textureGather(2, screen_texture, filtering_sampler, q));
+    let diff0 = color_edge_space(vec3f(cr.w, cg.w, cb.w)) - color_edge_space(vec3f(cr.y, cg.y, cb.y));
+    let diff1 = color_edge_space(vec3f(cr.z, cg.z, cb.z)) - color_edge_space(vec3f(cr.x, cg.x, cb.x));
+    let grad = sqrt(dot(diff0, diff0) + dot(diff1, diff1));
 #else
-    let c00 = prepass_color(uv);
-    let c10 = prepass_color(uv + vec2f(offset.x, 0.0));
-    let c01 = prepass_color(uv + vec2f(0.0, offset.y));
-    let c11 = prepass_color(uv + offset);
+    let c00 = edge_color_sample(uv);
+    let c10 = edge_color_sample(uv + vec2f(offset.x, 0.0));
+    let c01 = edge_color_sample(uv + vec2f(0.0, offset.y));
+    let c11 = edge_color_sample(uv + offset);
 
     let diff0 = c00 - c11;
     let diff1 = c10 - c01;
     let grad = sqrt(dot(diff0, diff0) + dot(diff1, diff1));
 #endif
 
-    return f32(grad > ed_uniform.color_threshold);
+    return edge_step(ed_uniform.color_threshold * threshold_scale, grad);
 }
+#endif  // NO_COLOR_SOURCE
+
+// -----------------------
+// Custom Edge Source -----
+// -----------------------
+
+#ifdef CUSTOM_EDGE_SOURCE
+/// Feed the standard samples at `uv` to the user's `custom_edge_value`.
+/// Without a prepass (COLOR_ONLY) `view_z` is 0 and `normal` is zero.
+fn custom_value(uv: vec2f) -> f32 {
+    let color = textureSample(screen_texture, filtering_sampler, uv);
+#ifdef COLOR_ONLY
+    return custom_edge_value(uv, 0.0, vec4f(0.0), color);
+#else
+    return custom_edge_value(uv, prepass_view_z(uv), prepass_normal_raw(uv), color);
+#endif
+}
+
+/// Gradient of the custom scalar, using the active operator's kernel shape.
+fn detect_edge_custom(uv: vec2f, thickness: f32) -> f32 {
+    let offset = thickness_offset(thickness);
+
+#ifdef OPERATOR_SOBEL
+#ifdef KERNEL_LAPLACIAN
+    let v_c = custom_value(uv);
+    let v_t = custom_value(uv + vec2f(      0.0,  offset.y));
+    let v_l = custom_value(uv + vec2f(-offset.x,       0.0));
+    let v_r = custom_value(uv + vec2f( offset.x,       0.0));
+    let v_b = custom_value(uv + vec2f(      0.0, -offset.y));
+
+    let grad = LAPLACIAN_GAIN * abs(v_t + v_l + v_r + v_b - 4.0*v_c);
+#else
+    let v_tl = custom_value(uv + vec2f(-offset.x,  offset.y));
+    let v_t  = custom_value(uv + vec2f(      0.0,  offset.y));
+    let v_tr = custom_value(uv + vec2f( offset.x,  offset.y));
+    let v_l  = custom_value(uv + vec2f(-offset.x,       0.0));
+    let v_r  = custom_value(uv + vec2f( offset.x,       0.0));
+    let v_bl = custom_value(uv + vec2f(-offset.x, -offset.y));
+    let v_b  = custom_value(uv + vec2f(      0.0, -offset.y));
+    let v_br = custom_value(uv + vec2f( offset.x, -offset.y));
+
+    let gx = KERNEL_CORNER*(-v_tl - v_bl + v_tr + v_br) + KERNEL_SIDE*(v_r - v_l);
+    let gy = KERNEL_CORNER*(-v_tl - v_tr + v_bl + v_br) + KERNEL_SIDE*(v_b - v_t);
+    let grad = max(abs(gx), abs(gy));
+#endif
+#else
+    let v00 = custom_value(uv);
+    let v10 = custom_value(uv + vec2f(offset.x, 0.0));
+    let v01 = custom_value(uv + vec2f(0.0, offset.y));
+    let v11 = custom_value(uv + offset);
+
+    let grad = max(abs(v00 - v11), abs(v10 - v01));
+#endif
+
+    return edge_step(ed_uniform.custom_threshold * threshold_scale, grad);
+}
+#endif
 
 // -----------------------
 // Channel Mask Decoding --
@@ -263,6 +1124,7 @@ fn decode_edge_mask(alpha: f32) -> vec2<bool> {
 // -----------------------
 
 #ifdef OPERATOR_PIXEL_ART
+#ifndef COLOR_ONLY
 
 /// UDLR pairwise normal comparison for crease detection.
 /// Directional tie-breaking: only marks one side to ensure 1px crease edges.
@@ -273,7 +1135,8 @@ fn check_crease(uv: vec2f, offset: vec2f) -> bool {
     let raw_neighbor = prepass_normal_raw(uv + offset);
     let n_neighbor = raw_neighbor.xyz;
     let diff = n_center - n_neighbor;
-    if (dot(diff, diff) <= ed_uniform.normal_threshold * ed_uniform.normal_threshold) {
+    let normal_threshold = ed_uniform.normal_threshold * threshold_scale / rim_boost;
+    if (dot(diff, diff) <= normal_threshold * normal_threshold) {
         return false;
     }
     // If neighbor is SKIP, center always wins (SKIP side won't draw any edge).
@@ -286,8 +1149,105 @@ fn check_crease(uv: vec2f, offset: vec2f) -> bool {
     return sum_c >= sum_n;
 }
 
+#endif  // COLOR_ONLY
 #endif
 
+// -----------------------
+// Edge Color Animation ---
+// -----------------------
+
+const TAU: f32 = 6.28318530718;
+
+/// Rotate hue by rotating the color around the gray (1,1,1) axis.
+fn rotate_hue(c: vec3f, angle: f32) -> vec3f {
+    let k = vec3f(0.57735027);
+    let cos_a = cos(angle);
+    return c * cos_a + cross(k, c) * sin(angle) + k * dot(k, c) * (1.0 - cos_a);
+}
+
+fn animate_edge_color(c: vec4f) -> vec4f {
+    let phase = ed_uniform.time * ed_uniform.edge_color_anim_speed * TAU;
+    if (ed_uniform.edge_color_anim_mode == 1u) {
+        return mix(c, ed_uniform.edge_color_anim_alt, 0.5 - 0.5 * cos(phase));
+    } else if (ed_uniform.edge_color_anim_mode == 2u) {
+        return vec4f(max(rotate_hue(c.rgb, phase), vec3f(0.0)), c.a);
+    }
+    return c;
+}
+
+// -----------------------
+// Auto Contrast ----------
+// -----------------------
+
+fn luminance(c: vec3f) -> f32 {
+    return dot(c, vec3f(0.2126, 0.7152, 0.0722));
+}
+
+/// Black or white, whichever contrasts more with background luminance `bg`.
+fn contrast_color(bg: f32) -> vec4f {
+    // 0.18 is middle gray in linear space.
+    return select(vec4f(1.0), vec4f(0.0, 0.0, 0.0, 1.0), bg > 0.18);
+}
+
+#ifndef NO_COLOR_SOURCE
+/// Black or white, whichever contrasts more with the background around `uv`.
+/// Samples 2 texels out diagonally so the edge pixel itself doesn't dominate.
+fn auto_contrast_color(uv: vec2f) -> vec4f {
+    let o = texel_size * 2.0;
+    let bg = 0.25 * (
+        luminance(prepass_color(uv + vec2f(o.x, o.y))) +
+        luminance(prepass_color(uv + vec2f(-o.x, o.y))) +
+        luminance(prepass_color(uv + vec2f(o.x, -o.y))) +
+        luminance(prepass_color(uv + vec2f(-o.x, -o.y)))
+    );
+    return contrast_color(bg);
+}
+#endif
+
+// -----------------------
+// Output Quantization ----
+// -----------------------
+
+fn linear_to_srgb(c: vec3f) -> vec3f {
+    return select(1.055 * pow(c, vec3f(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3f(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3f) -> vec3f {
+    return select(pow((c + 0.055) / 1.055, vec3f(2.4)), c / 12.92, c <= vec3f(0.04045));
+}
+
+/// Quantize to `output_levels` per channel in sRGB space, like a low bit-depth framebuffer.
+fn quantize_output(c: vec3f) -> vec3f {
+    let levels = ed_uniform.output_levels;
+    if (all(levels == vec3f(0.0))) {
+        return c;
+    }
+    let srgb = linear_to_srgb(saturate3(c));
+    let q = select(round(srgb * levels) / max(levels, vec3f(1.0)), srgb, levels == vec3f(0.0));
+    return srgb_to_linear(q);
+}
+
+fn saturate3(x: vec3f) -> vec3f { return clamp(x, vec3f(0.0), vec3f(1.0)); }
+
+// -----------------------
+// UV Distortion ----------
+// -----------------------
+
+/// Noise texture coordinate for a screen-space sample position.
+/// `distortion_phase` shifts the lookup (in noise tiles) along a golden-ratio
+/// diagonal so cameras with different phases sample uncorrelated noise.
+/// The sample position is rotated by `distortion_rotation` first, so the per-axis
+/// frequencies apply along rotated axes (diagonal wobble). `uv_distortion_speed`
+/// scrolls the lookup over time for an animated, "boiling" line.
+fn distortion_noise_uv(sample_uv: vec2f) -> vec2f {
+    let c = cos(ed_uniform.distortion_rotation);
+    let s = sin(ed_uniform.distortion_rotation);
+    let rotated = mat2x2f(c, s, -s, c) * sample_uv;
+    return rotated * ed_uniform.uv_distortion.xy
+        + ed_uniform.distortion_phase * vec2f(1.0, 0.61803399)
+        + ed_uniform.time * ed_uniform.uv_distortion_speed;
+}
+
 fn pixelate_uv(uv: vec2f, dims: vec2f, block_px: f32) -> vec2f {
     let b = max(block_px, 1.0);
     let cell = floor(uv * dims / b);
@@ -295,24 +1255,296 @@ fn pixelate_uv(uv: vec2f, dims: vec2f, block_px: f32) -> vec2f {
     return center;
 }
 
+// -----------------------
+// Coverage Thickness -----
+// -----------------------
+
+#ifdef COVERAGE_THICKNESS
+/// Fraction of the pixel at distance `d` texels covered by an outline `thickness`
+/// texels wide: 1 inside, a linear ramp over the last texel, 0 beyond.
+fn coverage_weight(d: f32, thickness: f32) -> f32 {
+    return saturate(0.5 * thickness + 0.5 - d);
+}
+
+/// 1-texel edge of `source` at `uv`: 0 = depth, 1 = normal, 2 = color.
+fn base_edge(uv: vec2f, source: u32, fresnel: f32) -> f32 {
+#ifndef COLOR_ONLY
+    if (source == 0u) {
+        return detect_edge_depth(uv, 1.0, fresnel);
+    }
+    if (source == 1u) {
+        return detect_edge_normal(uv, 1.0);
+    }
+#endif
+#ifndef NO_COLOR_SOURCE
+    return detect_edge_color(uv, 1.0);
+#else
+    return 0.0;
+#endif
+}
+
+/// Anti-aliased thick edge: the 1-texel edge is dilated to `thickness` by taking
+/// the coverage-weighted max over a disc of neighbors, giving fractional values
+/// along the outline's outer boundary.
+fn coverage_edge(uv: vec2f, thickness: f32, source: u32, fresnel: f32) -> f32 {
+    let steps = i32(ceil(0.5 * thickness));
+    var coverage = 0.0;
+    for (var y = -steps; y <= steps; y++) {
+        for (var x = -steps; x <= steps; x++) {
+            let o = vec2f(f32(x), f32(y));
+            let w = coverage_weight(length(o), thickness);
+            coverage = max(coverage, w * base_edge(uv + o * texel_size, source, fresnel));
+        }
+    }
+    return coverage;
+}
+#endif
+
+// -----------------------
+// Edge Direction ---------
+// -----------------------
+
+/// Scalar field whose gradient gives the edge direction: view z when a prepass is
+/// bound, luminance otherwise.
+fn direction_scalar(uv: vec2f) -> f32 {
+#ifdef COLOR_ONLY
+    return luminance(prepass_color(uv));
+#else
+    return prepass_view_z(uv);
+#endif
+}
+
+/// 3x3 Sobel gradient of `direction_scalar` in UV space (+x right, +y down).
+fn edge_gradient(uv: vec2f) -> vec2f {
+    let o = texel_size;
+    let s_tl = direction_scalar(uv + vec2f(-o.x, -o.y));
+    let s_t  = direction_scalar(uv + vec2f( 0.0, -o.y));
+    let s_tr = direction_scalar(uv + vec2f( o.x, -o.y));
+    let s_l  = direction_scalar(uv + vec2f(-o.x,  0.0));
+    let s_r  = direction_scalar(uv + vec2f( o.x,  0.0));
+    let s_bl = direction_scalar(uv + vec2f(-o.x,  o.y));
+    let s_b  = direction_scalar(uv + vec2f( 0.0,  o.y));
+    let s_br = direction_scalar(uv + vec2f( o.x,  o.y));
+
+    let gx = -s_tl - 2.0*s_l - s_bl + s_tr + 2.0*s_r + s_br;
+    let gy = -s_tl - 2.0*s_t - s_tr + s_bl + 2.0*s_b + s_br;
+    return vec2f(gx, gy);
+}
+
+/// Edge direction encoded as RG = normalize(gradient) * 0.5 + 0.5.
+/// Non-edge pixels (and flat gradients) encode as (0.5, 0.5).
+fn edge_direction_encoded(uv: vec2f, edge: f32) -> vec2f {
+    if (edge <= 0.0) {
+        return vec2f(0.5);
+    }
+    let g = edge_gradient(uv);
+    let len = length(g);
+    if (len < 1e-6) {
+        return vec2f(0.5);
+    }
+    return (g / len) * 0.5 + 0.5;
+}
+
+#ifdef EDGE_COLOR_BY_DIRECTION
+/// EdgeColorMode::Direction: `color` blended toward `edge_color_secondary` by
+/// `gradient.y² / |gradient|²` (0 on vertical lines, 1 on horizontal ones).
+fn edge_color_by_direction(uv: vec2f, color: vec4f) -> vec4f {
+    let g = edge_gradient(uv);
+    let len_sq = dot(g, g);
+    if (len_sq < 1e-12) {
+        return color;
+    }
+    return mix(color, ed_uniform.edge_color_secondary, g.y * g.y / len_sq);
+}
+#endif
+
+#ifdef MOTION_VECTORS
+// -----------------------
+// Motion Vectors ---------
+// -----------------------
+
+/// UV-space motion at `uv` since last frame: the previous position is `uv - motion`.
+fn prepass_motion(uv: vec2f) -> vec2f {
+    let pixel_coord = vec2i(border_clamp_uv(uv) * texture_size);
+#ifdef MULTISAMPLED
+    return textureLoad(motion_vector_prepass_texture, pixel_coord, sample_index_i).xy;
+#else
+    return textureLoad(motion_vector_prepass_texture, pixel_coord, 0).xy;
+#endif
+}
+
+/// Screen velocity at `uv` in pixels per frame.
+fn prepass_motion_px(uv: vec2f) -> f32 {
+    return length(prepass_motion(uv) * texture_size);
+}
+#endif
+
+#ifdef EDGE_COLOR_FROM_NORMAL
+// -----------------------
+// Normal Edge Color ------
+// -----------------------
+
+/// Matcap lookup: the view-space normal's xy (y up) mapped to [0, 1] UV, or the
+/// world-space normal's with NORMAL_SPACE_WORLD.
+fn edge_color_from_normal(uv: vec2f, alpha: f32) -> vec4f {
+    let n = prepass_normal_unpack(uv);
+#ifdef NORMAL_SPACE_WORLD
+    let n_lookup = n;
+#else
+    let n_lookup = normalize((view.view_from_world * vec4f(n, 0.0)).xyz);
+#endif
+    let lookup_uv = vec2f(n_lookup.x, -n_lookup.y) * 0.5 + 0.5;
+    let c = textureSampleLevel(normal_color_texture, filtering_sampler, lookup_uv, 0.0);
+    return vec4f(c.rgb, alpha);
+}
+#endif
+
+// -----------------------
+// Threshold Vignette -----
+// -----------------------
+
+/// Threshold multiplier at `uv`: 1 inside `vignette_radius`, ramping to
+/// `1 + threshold_vignette` at the screen corners (distance normalized so corners are 1).
+fn vignette_threshold_scale(uv: vec2f) -> f32 {
+    let d = length(uv - 0.5) * 1.41421356;
+    let falloff = smoothstep(ed_uniform.vignette_radius, 1.0, d);
+    return max(1.0 + ed_uniform.threshold_vignette * falloff, 0.0);
+}
+
+#ifndef COLOR_ONLY
+// -----------------------
+// Distance Fade ----------
+// -----------------------
+
+/// Depth of the nearest surface at `uv` and its four neighbors `radius` texels away,
+/// so silhouette pixels over the background still see the object's depth.
+fn nearest_prepass_depth(uv: vec2f, radius: f32) -> f32 {
+    let offset = texel_size * max(radius, 1.0);
+    // Reverse-Z: the nearest sample has the largest depth.
+    return max(
+        max(prepass_depth(uv), prepass_depth(uv + vec2f(offset.x, 0.0))),
+        max(
+            max(prepass_depth(uv - vec2f(offset.x, 0.0)), prepass_depth(uv + vec2f(0.0, offset.y))),
+            prepass_depth(uv - vec2f(0.0, offset.y)),
+        ),
+    );
+}
+
+/// Edge strength multiplier at `uv`: 1 up to `edge_fade_start`, 0 from
+/// `edge_fade_end`, linear in view distance in between. Uses the nearest depth of
+/// the pixel and its neighbors at `radius` texels, so silhouette pixels that land
+/// on the background side of a near object aren't faded as if at infinity.
+fn edge_distance_fade(uv: vec2f, radius: f32) -> f32 {
+    let span = ed_uniform.edge_fade_end - ed_uniform.edge_fade_start;
+    if (span <= 0.0) {
+        return 1.0;
+    }
+    let depth = nearest_prepass_depth(uv, radius);
+    if (depth <= 0.0) {
+        return 0.0;
+    }
+    let view_dist = abs(depth_ndc_to_view_z(depth));
+    return 1.0 - saturate((view_dist - ed_uniform.edge_fade_start) / span);
+}
+
+/// Thickness multiplier for `thickness_depth_falloff`: `1 / (1 + falloff * distance)`
+/// for positive values (thinner far away), `1 - falloff * distance` (capped at 4x)
+/// for negative ones. The distance goes through `depth_ndc_to_view_z`, so it is
+/// linear under both perspective and orthographic projections.
+fn thickness_depth_scale(uv: vec2f, radius: f32) -> f32 {
+    let falloff = ed_uniform.thickness_depth_falloff;
+    if (falloff == 0.0) {
+        return 1.0;
+    }
+    let depth = nearest_prepass_depth(uv, radius);
+    if (depth <= 0.0) {
+        return 1.0;
+    }
+    let view_dist = abs(depth_ndc_to_view_z(depth));
+    if (falloff > 0.0) {
+        return 1.0 / (1.0 + falloff * view_dist);
+    }
+    return min(1.0 - falloff * view_dist, 4.0);
+}
+#endif  // COLOR_ONLY
+
+const EDGE_SOURCE_OTHER: u32 = 0u;
+const EDGE_SOURCE_DEPTH: u32 = 1u;
+const EDGE_SOURCE_NORMAL: u32 = 2u;
+const EDGE_SOURCE_COLOR: u32 = 3u;
+
+/// DEBUG_CHANNEL_COLORS tint: red depth, green normal, blue color, white for the
+/// custom source and the screen border.
+fn edge_source_color(source: u32) -> vec3f {
+    switch source {
+        case EDGE_SOURCE_DEPTH: { return vec3f(1.0, 0.0, 0.0); }
+        case EDGE_SOURCE_NORMAL: { return vec3f(0.0, 1.0, 0.0); }
+        case EDGE_SOURCE_COLOR: { return vec3f(0.0, 0.0, 1.0); }
+        default: { return vec3f(1.0); }
+    }
+}
+
+/// DEBUG_EDGE_STRENGTH_HEATMAP ramp: dark blue (0) through cyan and yellow to red (1).
+fn heatmap(t: f32) -> vec3f {
+    let x = 4.0 * saturate(t);
+    return vec3f(saturate(1.5 - abs(x - 3.0)), saturate(1.5 - abs(x - 2.0)), saturate(1.5 - abs(x - 1.0)));
+}
+
 var<private> texture_size: vec2f;
 var<private> texel_size: vec2f;
+var<private> threshold_scale: f32 = 1.0;
+var<private> rim_boost: f32 = 1.0;
+var<private> thickness_scale: f32 = 1.0;
 var<private> sample_index_i: i32 = 0;
 
+/// Detector thickness after `thickness_depth_falloff`, never thinned below one texel
+/// (or the configured thickness, if smaller).
+fn scaled_thickness(thickness: f32) -> f32 {
+    return max(thickness * thickness_scale, min(thickness, 1.0));
+}
+
+struct FragmentOutput {
+    @location(0) color: vec4f,
+#ifdef EDGE_DIRECTION_TARGET
+    // Secondary target (ViewEdgeDirectionTexture): RG = encoded edge direction.
+    @location(1) direction: vec4f,
+#endif
+#ifdef OUTPUT_MASK
+    // Extra target (EdgeDetectionMask): R = edge strength.
+    @location(2) mask: vec4f,
+#endif
+#ifdef TEMPORAL_HISTORY
+    // This frame's ViewEdgeHistoryTextures::write: R = edge strength.
+    @location(3) history: vec4f,
+#endif
+}
+
 @fragment
 fn fragment(
 #ifdef MULTISAMPLED
     @builtin(sample_index) sample_index: u32,
 #endif
     in: FullscreenVertexOutput
-) -> @location(0) vec4f {
+) -> FragmentOutput {
 #ifdef MULTISAMPLED
     sample_index_i = i32(sample_index);
 #endif
 
+#ifdef NO_COLOR_SOURCE
+    // The prepass textures match the main texture size.
+    texture_size = vec2f(textureDimensions(depth_prepass_texture));
+#else
     texture_size = vec2f(textureDimensions(screen_texture, 0));
+#endif
     texel_size = 1.0 / texture_size;
+    threshold_scale = vignette_threshold_scale(in.uv);
+#ifdef EDGE_IMPORTANCE
+    threshold_scale /= max(textureSampleLevel(importance_texture, filtering_sampler, in.uv, 0.0).r, 1e-4);
+#endif
 
+#ifdef COLOR_ONLY
+    let fresnel = 0.0;
+#else
     let near_ndc_pos = vec3f(uv_to_ndc(in.uv), 1.0);
     let near_world_pos = position_ndc_to_world(near_ndc_pos);
 
@@ -320,19 +1552,36 @@ fn fragment(
     
     let normal = prepass_normal_unpack(in.uv);
     let fresnel = 1.0 - saturate(dot(normal, view_direction));
+    rim_boost = 1.0 + ed_uniform.rim_strength * (1.0 - abs(dot(normal, view_direction)));
+#endif
 
-    let sample_uv = in.position.xy * min(texel_size.x, texel_size.y);
-    let noise = textureSample(noise_texture, noise_sampler, sample_uv * ed_uniform.uv_distortion.xy);
+    // From uv rather than the fragment position, so a reduced-resolution pass
+    // samples the same noise.
+    let sample_uv = in.uv * texture_size * min(texel_size.x, texel_size.y);
+    let noise = textureSample(noise_texture, noise_sampler, distortion_noise_uv(sample_uv));
 
     let uv_noise = in.uv + noise.xy * ed_uniform.uv_distortion.zw;
     let block_pixel = max(f32(ed_uniform.block_pixel), 1.0);
     let uv_noise_px = pixelate_uv(uv_noise, texture_size, f32(block_pixel));
     let uv_px = pixelate_uv(in.uv, texture_size, f32(block_pixel));
+#ifndef COLOR_ONLY
+    thickness_scale = thickness_depth_scale(
+        uv_noise_px,
+        max(ed_uniform.depth_thickness, ed_uniform.normal_thickness),
+    );
+#endif
 
     var edge = 0.0;
     var resolved_edge_color = ed_uniform.edge_color;
-
-#ifdef OPERATOR_PIXEL_ART
+    // Which source produced `edge`, for DEBUG_CHANNEL_COLORS.
+    var edge_source = EDGE_SOURCE_OTHER;
+
+#ifdef SILHOUETTE_HALO
+    // Selection highlight: only the outward silhouette of the depth prepass.
+    edge = silhouette_halo(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness));
+    resolved_edge_color = ed_uniform.silhouette_color;
+    edge_source = EDGE_SOURCE_DEPTH;
+#else ifdef OPERATOR_PIXEL_ART
     // PixelArt operator: UDLR pairwise comparison with silhouette/crease priority.
     let px_offset = vec2f(block_pixel, block_pixel) / texture_size;
 
@@ -344,7 +1593,9 @@ fn fragment(
     );
 
     // Decode per-entity edge mask from normal prepass alpha
+#ifndef COLOR_ONLY
     let mask = decode_edge_mask(prepass_normal_raw(uv_noise_px).a);
+#endif
 
     var is_silhouette = false;
     var is_crease = false;
@@ -356,9 +1607,8 @@ fn fragment(
     if (mask.x) {
         let center_z = prepass_view_z(uv_noise_px);
         let view_z = abs(center_z);
-        let steep_adj = smoothstep(ed_uniform.steep_angle_threshold, 1.0, fresnel)
-                        * ed_uniform.steep_angle_multiplier * view_z;
-        let threshold = ed_uniform.depth_threshold * (1.0 + steep_adj);
+        let steep_adj = steep_angle_adjustment(fresnel, view_z);
+        let threshold = depth_threshold_at(view_z) * (1.0 + steep_adj);
 
         for (var i = 0; i < 4; i++) {
             let neighbor_z = prepass_view_z(uv_noise_px + offsets[i]);
@@ -400,51 +1650,120 @@ fn fragment(
     if (is_silhouette) {
         edge = 1.0;
         resolved_edge_color = ed_uniform.silhouette_color;
+        edge_source = EDGE_SOURCE_DEPTH;
     } else if (is_crease) {
         edge = 1.0;
         resolved_edge_color = ed_uniform.crease_color;
+        edge_source = EDGE_SOURCE_NORMAL;
     }
 
 #ifdef ENABLE_COLOR
-    if (edge < 1.0) {
-        let edge_color_val = detect_edge_color(uv_noise_px, ed_uniform.color_thickness);
+#ifdef SKIP_TRANSPARENT
+    let color_edges_allowed = prepass_depth(uv_noise_px) > 0.0;
+#else
+    let color_edges_allowed = true;
+#endif
+    if (edge < 1.0 && color_edges_allowed) {
+        let edge_color_val = detect_edge_color(uv_noise_px, scaled_thickness(ed_uniform.color_thickness));
         if (edge_color_val > 0.0) {
             edge = 1.0;
-            resolved_edge_color = ed_uniform.edge_color;
+            resolved_edge_color = ed_uniform.color_edge_color;
+            edge_source = EDGE_SOURCE_COLOR;
         }
     }
 #endif
 
 #else
     // Sobel / Roberts Cross operators with silhouette/crease priority.
-#ifdef ENABLE_DEPTH
-    let edge_depth = detect_edge_depth(uv_noise_px, ed_uniform.depth_thickness, fresnel);
-    if (edge_depth > 0.0) {
+#ifdef JOINT_DEPTH_NORMAL
+    let joint = detect_edge_joint(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness));
+    if (joint == 1u) {
         edge = 1.0;
         resolved_edge_color = ed_uniform.silhouette_color;
+        edge_source = EDGE_SOURCE_DEPTH;
+    } else if (joint == 2u) {
+        edge = 1.0;
+        resolved_edge_color = ed_uniform.crease_color;
+        edge_source = EDGE_SOURCE_NORMAL;
+    }
+#else
+    // With COVERAGE_THICKNESS the values are fractional coverage; a later source
+    // only takes over where its coverage is higher.
+#ifdef ENABLE_DEPTH
+#ifdef COVERAGE_THICKNESS
+    var edge_depth = coverage_edge(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness), 0u, fresnel);
+#else
+    var edge_depth = detect_edge_depth(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness), fresnel);
+#endif
+#ifdef OUTLINE_AGAINST_BACKGROUND
+    edge_depth = max(edge_depth, background_edge(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness)));
+#endif
+    if (edge_depth > edge) {
+        edge = edge_depth;
+        resolved_edge_color = ed_uniform.silhouette_color;
+        edge_source = EDGE_SOURCE_DEPTH;
     }
 #endif
 
 #ifdef ENABLE_NORMAL
     if (edge < 1.0) {
-        let edge_normal = detect_edge_normal(uv_noise_px, ed_uniform.normal_thickness);
-        if (edge_normal > 0.0) {
-            edge = 1.0;
+#ifdef COVERAGE_THICKNESS
+        let edge_normal = coverage_edge(uv_noise_px, scaled_thickness(ed_uniform.normal_thickness), 1u, fresnel);
+#else
+        let edge_normal = detect_edge_normal(uv_noise_px, scaled_thickness(ed_uniform.normal_thickness));
+#endif
+        if (edge_normal > edge) {
+            edge = edge_normal;
+            resolved_edge_color = ed_uniform.crease_color;
+            edge_source = EDGE_SOURCE_NORMAL;
+        }
+    }
+#endif
+#endif  // JOINT_DEPTH_NORMAL
+
+#ifdef ENABLE_CREASE
+    // Depth creases after depth edges, so silhouettes keep their color.
+    if (edge < 1.0) {
+        let edge_crease = detect_edge_crease(uv_noise_px, scaled_thickness(ed_uniform.depth_thickness));
+        if (edge_crease > edge) {
+            edge = edge_crease;
             resolved_edge_color = ed_uniform.crease_color;
+            edge_source = EDGE_SOURCE_NORMAL;
         }
     }
 #endif
 
 #ifdef ENABLE_COLOR
+#ifdef SKIP_TRANSPARENT
+    // Only transparent geometry (or nothing) covers pixels with an empty prepass.
+    let color_edges_allowed = prepass_depth(uv_noise_px) > 0.0;
+#else
+    let color_edges_allowed = true;
+#endif
+    if (edge < 1.0 && color_edges_allowed) {
+#ifdef COVERAGE_THICKNESS
+        let edge_color_val = coverage_edge(uv_noise_px, scaled_thickness(ed_uniform.color_thickness), 2u, fresnel);
+#else
+        let edge_color_val = detect_edge_color(uv_noise_px, scaled_thickness(ed_uniform.color_thickness));
+#endif
+        if (edge_color_val > edge) {
+            edge = edge_color_val;
+            resolved_edge_color = ed_uniform.color_edge_color;
+            edge_source = EDGE_SOURCE_COLOR;
+        }
+    }
+#endif
+#endif  // SILHOUETTE_HALO / OPERATOR_PIXEL_ART
+
+#ifdef CUSTOM_EDGE_SOURCE
     if (edge < 1.0) {
-        let edge_color_val = detect_edge_color(uv_noise_px, ed_uniform.color_thickness);
-        if (edge_color_val > 0.0) {
+        if (detect_edge_custom(uv_noise_px, scaled_thickness(ed_uniform.custom_thickness)) > 0.0) {
             edge = 1.0;
             resolved_edge_color = ed_uniform.edge_color;
+            edge_source = EDGE_SOURCE_OTHER;
         }
     }
 #endif
-#endif  // OPERATOR_PIXEL_ART
 
     // Edge mask: suppress edges on pixels marked with alpha=0.0 in normal prepass.
     // Materials using the NoEdgeExtension write alpha=0.0 (e.g. hex tile surfaces).
@@ -455,6 +1774,10 @@ fn fragment(
     // the 4-level alpha encoding (SKIP/SILHOUETTE_ONLY/CREASE_ONLY/BOTH).
     // This legacy suppression uses alpha < 0.5 as "no-edge", which would incorrectly
     // suppress SILHOUETTE_ONLY (alpha=0.25). Skip it for PixelArt.
+    // Without a prepass (COLOR_ONLY) there is no mask to read.
+    // The silhouette halo lies on background pixels and has neither.
+#ifndef COLOR_ONLY
+#ifndef SILHOUETTE_HALO
 #ifndef OPERATOR_PIXEL_ART
     if (edge > 0.0) {
         let center_raw = prepass_normal_raw(uv_noise_px);
@@ -493,12 +1816,176 @@ fn fragment(
             edge = 0.0;
         }
     }
+#endif  // SILHOUETTE_HALO
+#endif  // COLOR_ONLY
 
-    let src = textureSample(screen_texture, filtering_sampler, uv_px);
-    // Blend with resolved edge color, respecting its alpha for opacity control.
-    var color = mix(src.rgb, resolved_edge_color.rgb, edge * resolved_edge_color.a);
+#ifdef MOTION_GATE
+    // Only moving objects keep their outlines.
+    if (edge > 0.0 && prepass_motion_px(uv_noise_px) < ed_uniform.motion_threshold) {
+        edge = 0.0;
+    }
+#endif
+
+#ifdef REGION_MASK
+    if (edge > 0.0) {
+        edge *= saturate(textureSampleLevel(region_mask_texture, filtering_sampler, uv_noise_px, 0.0).r);
+    }
+#endif
+
+#ifndef COLOR_ONLY
+    if (edge > 0.0) {
+        edge *= edge_distance_fade(uv_noise_px, max(ed_uniform.depth_thickness, ed_uniform.normal_thickness));
+    }
+#endif
+
+#ifdef EDGE_COLOR_FROM_NORMAL
+    if (edge > 0.0) {
+        resolved_edge_color = edge_color_from_normal(uv_noise_px, resolved_edge_color.a);
+    }
+#endif
+
+#ifdef EDGE_COLOR_BY_DIRECTION
+    if (edge > 0.0) {
+        resolved_edge_color = edge_color_by_direction(uv_noise_px, resolved_edge_color);
+    }
+#endif
+
+#ifdef BORDER_DRAW
+    // Frame outline along the viewport edge, as wide as the thickest edge source.
+    let border_px = max(max(ed_uniform.depth_thickness, ed_uniform.normal_thickness), ed_uniform.color_thickness);
+    let px = in.position.xy - view.viewport.xy;
+    let border_dist = min(min(px.x, px.y), min(view.viewport.z - px.x, view.viewport.w - px.y));
+    if (border_dist < border_px) {
+        edge = 1.0;
+        resolved_edge_color = ed_uniform.edge_color;
+        edge_source = EDGE_SOURCE_OTHER;
+    }
+#endif
+
+    resolved_edge_color = animate_edge_color(resolved_edge_color);
+
+#ifdef USER_EDGE_POSTPROCESS
+    edge = user_edge_postprocess(uv_noise_px, edge);
+#endif
+#ifdef USER_EDGE_COLOR
+    resolved_edge_color = user_edge_color(uv_noise_px, edge, resolved_edge_color);
+#endif
+
+#ifdef EDGE_EMISSIVE
+    // HDR targets only: over-bright edges feed a later bloom pass.
+    resolved_edge_color = vec4f(resolved_edge_color.rgb * ed_uniform.edge_emissive_strength, resolved_edge_color.a);
+#endif
+
+#ifdef TEMPORAL_HISTORY
+    // Blend with last frame's edge strength where this pixel was; off-screen
+    // history is discarded. Stored before global_intensity so fades don't compound.
+    let history_uv = in.uv - prepass_motion(in.uv);
+    if (all(history_uv >= vec2f(0.0)) && all(history_uv <= vec2f(1.0))) {
+        let history = textureSampleLevel(edge_history_texture, filtering_sampler, history_uv, 0.0).r;
+        edge = mix(edge, history, ed_uniform.temporal_blend);
+    }
+    let history_edge = edge;
+#endif
+
+    // Outside viewport_rect the source passes through. Not an early return: the
+    // detectors above sample with implicit derivatives, which need uniform control flow.
+    // Applied after the history blend and not stored in it, so history keeps tracking
+    // the edges outside the rect and they don't fade back in when it moves.
+    let rect = ed_uniform.viewport_rect;
+    if (any(in.uv < rect.xy) || any(in.uv > rect.zw)) {
+        edge = 0.0;
+    }
+
+    // Fades the whole effect: 0 leaves the scene untouched.
+    edge *= ed_uniform.global_intensity;
+
+    var out: FragmentOutput;
+
+#ifdef TEMPORAL_HISTORY
+    out.history = vec4f(history_edge, 0.0, 0.0, 1.0);
+#endif
+
+#ifdef TRANSPARENT_BACKGROUND
+    // Edges only: coverage goes to alpha so the output can be alpha-blended
+    // over another camera (EdgeDetectionOverlay).
+    out.color = vec4f(resolved_edge_color.rgb, edge * resolved_edge_color.a * ed_uniform.edge_opacity);
+#else
+#ifdef FLAT_BACKGROUND
+    // Line art: edges over a flat fill, the scene color is never read.
+    let src = ed_uniform.flat_background;
+    if (ed_uniform.auto_contrast != 0u && edge > 0.0) {
+        resolved_edge_color = contrast_color(luminance(src.rgb));
+    }
+#else
+    let src = ldr_clamp4(textureSample(screen_texture, filtering_sampler, uv_px));
+    if (ed_uniform.auto_contrast != 0u && edge > 0.0) {
+        resolved_edge_color = auto_contrast_color(uv_px);
+    }
+#endif
+    // Everything here is linear: uniform colors are converted on the CPU, sRGB
+    // sources decode on sample and sRGB targets encode on write, so no conversion
+    // is needed for either LDR or HDR views.
+    // Straight-alpha "over" of the resolved edge color onto the source, respecting
+    // its alpha for opacity control. Over an opaque source this is a plain mix.
+    // Over transparent pixels (render-to-texture, or a stacked camera cleared to
+    // transparent and composited with `CameraOutputMode::Write` blending) the edge
+    // color stays unpremultiplied, so the camera composite applies edge alpha once.
+    // edge_opacity 0 passes the source through unchanged, 1 is fully opaque edges.
+    let edge_alpha = edge * resolved_edge_color.a * ed_uniform.edge_opacity;
+#ifdef EDGE_BLEND_ADDITIVE
+    // EdgeBlendMode::Additive: glow on top of the scene, its alpha unchanged.
+    let out_alpha = src.a;
+    let over = src.rgb + resolved_edge_color.rgb * edge_alpha;
+#else
+    let out_alpha = edge_alpha + src.a * (1.0 - edge_alpha);
+    let over = (resolved_edge_color.rgb * edge_alpha + src.rgb * src.a * (1.0 - edge_alpha))
+        / max(out_alpha, 1e-6);
+#endif
+    // Preserve source alpha for compositing; fully opaque edges are fully opaque,
+    // except with EdgeBlendMode::Alpha.
+#ifdef EDGE_BLEND_ALPHA
+    let edge_out = vec4f(over, src.a);
+#else
+    let edge_out = vec4f(over, out_alpha);
+#endif
+    // No-edge pixels take the source unchanged: the division above rounds when
+    // src.a < 1, which showed up as a faint color shift away from the edges. They
+    // pass through bit-exact unless FORCE_LDR clamps them like the edges (an LDR
+    // target would store them clamped too) or output_bit_depth quantizes the frame.
+    out.color = select(src, ldr_clamp4(edge_out), edge_alpha > 0.0);
+    out.color = vec4f(quantize_output(out.color.rgb), out.color.a);
+#endif
+
+#ifdef EDGE_DIRECTION_TARGET
+    out.direction = vec4f(edge_direction_encoded(uv_noise_px, edge), 0.0, 1.0);
+#endif
+
+#ifdef OUTPUT_MASK
+    out.mask = vec4f(edge, 0.0, 0.0, 1.0);
+#endif
+
+#ifdef DEBUG_EDGE_DIRECTION
+    out.color = vec4f(edge_direction_encoded(uv_noise_px, edge), 0.0, 1.0);
+#endif
+
+#ifdef DEBUG_CHANNEL_COLORS
+    // Edges tinted by source over black: red depth, green normal, blue color.
+    out.color = vec4f(edge_source_color(edge_source) * edge, 1.0);
+#endif
+
+#ifdef DEBUG_EDGE_STRENGTH_HEATMAP
+    out.color = vec4f(heatmap(edge), 1.0);
+#endif
+
+#ifdef DEBUG_NORMALS
+    // Decoded world-space normal, packed as n * 0.5 + 0.5.
+    out.color = vec4f(prepass_normal(in.uv), 1.0);
+#endif
+
+#ifdef DEBUG_DISTORTION_FIELD
+    // Raw noise sample driving the UV offset (offset = noise.xy * strength).
+    out.color = vec4f(noise.xy, 0.0, 1.0);
+#endif
 
-    // Preserve source alpha for compositing (render-to-texture transparency).
-    // Where an edge is drawn, force opaque so outlines at entity boundaries are visible.
-    return vec4f(color, max(src.a, edge));
+    return out;
 }
\ No newline at end of file
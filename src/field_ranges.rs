@@ -0,0 +1,224 @@
+//! Per-field range metadata for [`EdgeDetection`], for editor integrations.
+//!
+//! [`EdgeDetection::FIELD_RANGES`] lists every numeric field with a documented
+//! range: the valid bounds (those [`EdgeDetection::clamp_to_valid_ranges`] clamps
+//! into) and a narrower slider range covering the useful values, which the
+//! `inspector` feature's slider bounds match. Fields are looked up by name through
+//! `Reflect`, so a generic inspector can build its sliders from the table:
+//!
+//! ```ignore
+//! for range in EdgeDetection::FIELD_RANGES {
+//!     if let Some(value) = edge_detection
+//!         .field_mut(range.name)
+//!         .and_then(|field| field.try_downcast_mut::<f32>())
+//!     {
+//!         ui.add(egui::Slider::new(value, range.slider_min..=range.slider_max).text(range.name));
+//!     }
+//! }
+//! ```
+
+use std::f32::consts::TAU;
+
+use crate::EdgeDetection;
+
+/// Range metadata of one numeric [`EdgeDetection`] field. Applies to each component
+/// of `Vec2` fields and to the inner value of `Option<f32>` fields; `u32` fields use
+/// the bounds rounded.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EdgeDetectionFieldRange {
+    /// Field name, as in the struct and its `Reflect` implementation.
+    pub name: &'static str,
+    /// Smallest valid value.
+    pub min: f32,
+    /// Largest valid value (`f32::INFINITY` when unbounded).
+    pub max: f32,
+    /// Lower end of a useful slider range.
+    pub slider_min: f32,
+    /// Upper end of a useful slider range.
+    pub slider_max: f32,
+}
+
+const fn range(
+    name: &'static str,
+    min: f32,
+    max: f32,
+    slider_min: f32,
+    slider_max: f32,
+) -> EdgeDetectionFieldRange {
+    EdgeDetectionFieldRange {
+        name,
+        min,
+        max,
+        slider_min,
+        slider_max,
+    }
+}
+
+const INF: f32 = f32::INFINITY;
+
+impl EdgeDetection {
+    /// Ranges of the numeric fields. See the [module docs](crate::field_ranges).
+    pub const FIELD_RANGES: &'static [EdgeDetectionFieldRange] = &[
+        range("depth_threshold", 0.0, INF, 0.0, 8.0),
+        range("depth_threshold_world_units", 0.0, INF, 0.0, 2.0),
+        range("depth_threshold_far", 0.0, INF, 0.0, 8.0),
+        range("depth_threshold_ramp", 0.0, INF, 0.0, 200.0),
+        range("normal_threshold", 0.0, INF, 0.0, 8.0),
+        range("crease_threshold", 0.0, INF, 0.0, 4.0),
+        range("color_threshold", 0.0, INF, 0.0, 8.0),
+        range("custom_threshold", 0.0, INF, 0.0, 8.0),
+        range("plane_threshold", 0.0, INF, 0.0, 2.0),
+        range("motion_threshold", 0.0, INF, 0.0, 4.0),
+        range("flat_rejection_threshold", 0.0, 1.0, 0.0, 1.0),
+        range("depth_thickness", 0.0, INF, 0.0, 8.0),
+        range("normal_thickness", 0.0, INF, 0.0, 8.0),
+        range("color_thickness", 0.0, INF, 0.0, 8.0),
+        range("custom_thickness", 0.0, INF, 0.0, 8.0),
+        range("thickness_axis_scale", 0.0, INF, 0.0, 4.0),
+        range("thickness_depth_falloff", -INF, INF, -0.5, 0.5),
+        range("steep_angle_threshold", 0.0, 1.0, 0.0, 1.0),
+        range("steep_angle_multiplier", 0.0, INF, 0.0, 1.0),
+        range("depth_bias", 0.0, INF, 0.0, 1.0),
+        range("depth_prefilter", 0.0, 4.0, 0.0, 4.0),
+        range("uv_distortion_frequency", 0.0, INF, 0.0, 16.0),
+        range("uv_distortion_strength", 0.0, INF, 0.0, 0.02),
+        range("uv_distortion_speed", -INF, INF, 0.0, 1.0),
+        range("distortion_phase", -INF, INF, 0.0, TAU),
+        range("distortion_rotation", -INF, INF, 0.0, TAU),
+        range("block_pixel", 1.0, INF, 1.0, 6.0),
+        range("kernel_radius", 1.0, 3.0, 1.0, 3.0),
+        range("threshold_vignette", -1.0, INF, -1.0, 4.0),
+        range("vignette_radius", 0.0, 1.0, 0.0, 1.0),
+        range("global_intensity", 0.0, 1.0, 0.0, 1.0),
+        range("edge_opacity", 0.0, 1.0, 0.0, 1.0),
+        range("edge_emissive_strength", 0.0, INF, 0.0, 16.0),
+        range("edge_fade_start", 0.0, INF, 0.0, 200.0),
+        range("edge_fade_end", 0.0, INF, 0.0, 200.0),
+        range("resolution_scale", 0.25, 1.0, 0.25, 1.0),
+        range("edge_dilation", 0.0, 8.0, 0.0, 8.0),
+        range("rim_strength", 0.0, INF, 0.0, 4.0),
+        range("edge_smoothness", 0.0, 1.0, 0.0, 1.0),
+        range("temporal_blend", 0.0, 0.95, 0.0, 0.95),
+    ];
+
+    /// The range of the field `name`, if it has one.
+    pub fn field_range(name: &str) -> Option<&'static EdgeDetectionFieldRange> {
+        Self::FIELD_RANGES.iter().find(|range| range.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{math::Vec2, reflect::Struct};
+
+    use super::*;
+
+    /// The value of an `f32` or `Option<f32>` field, looked up by name.
+    fn get(edge_detection: &mut EdgeDetection, name: &str) -> Option<f32> {
+        let field = edge_detection.field_mut(name)?;
+        if let Some(value) = field.try_downcast_ref::<f32>() {
+            Some(*value)
+        } else {
+            field.try_downcast_ref::<Option<f32>>().copied().flatten()
+        }
+    }
+
+    /// Sets an `f32` field, or the inner value of a `Some` `Option<f32>` field.
+    fn set(edge_detection: &mut EdgeDetection, name: &str, new: f32) {
+        let Some(field) = edge_detection.field_mut(name) else {
+            return;
+        };
+        if let Some(value) = field.try_downcast_mut::<f32>() {
+            *value = new;
+        } else if let Some(Some(value)) = field.try_downcast_mut::<Option<f32>>() {
+            *value = new;
+        }
+    }
+
+    #[test]
+    fn every_range_names_a_numeric_field() {
+        let mut edge_detection = EdgeDetection::default();
+        for range in EdgeDetection::FIELD_RANGES {
+            let field = edge_detection
+                .field_mut(range.name)
+                .unwrap_or_else(|| panic!("no field named {}", range.name));
+            assert!(
+                field.try_downcast_ref::<f32>().is_some()
+                    || field.try_downcast_ref::<Option<f32>>().is_some()
+                    || field.try_downcast_ref::<Vec2>().is_some()
+                    || field.try_downcast_ref::<u32>().is_some(),
+                "{} is not a numeric field",
+                range.name
+            );
+        }
+    }
+
+    #[test]
+    fn clamp_to_valid_ranges_uses_the_field_ranges() {
+        for out_of_range in [-1.0e9, 1.0e9] {
+            let mut edge_detection = EdgeDetection {
+                depth_threshold_far: Some(0.0),
+                depth_threshold_world_units: Some(0.0),
+                ..EdgeDetection::default()
+            };
+            for range in EdgeDetection::FIELD_RANGES {
+                set(&mut edge_detection, range.name, out_of_range);
+            }
+            let clamped = edge_detection.clamp_to_valid_ranges();
+            for range in EdgeDetection::FIELD_RANGES {
+                let Some(value) = get(&mut edge_detection, range.name) else {
+                    continue;
+                };
+                let expected = if clamped.contains(&range.name) {
+                    out_of_range.clamp(range.min, range.max)
+                } else {
+                    out_of_range
+                };
+                assert_eq!(value, expected, "{}", range.name);
+            }
+        }
+    }
+
+    #[cfg(feature = "inspector")]
+    #[test]
+    fn inspector_bounds_match_slider_ranges() {
+        use std::any::TypeId;
+
+        use bevy::reflect::{TypeRegistry, Typed};
+        use bevy_inspector_egui::inspector_options::{
+            ReflectInspectorOptions, Target, std_options::NumberOptions,
+        };
+
+        let mut registry = TypeRegistry::new();
+        registry.register::<EdgeDetection>();
+        let options = registry
+            .get_type_data::<ReflectInspectorOptions>(TypeId::of::<EdgeDetection>())
+            .expect("EdgeDetection has no InspectorOptions");
+        let info = EdgeDetection::type_info()
+            .as_struct()
+            .expect("EdgeDetection is a struct");
+        for (index, field) in info.iter().enumerate() {
+            let Some(field_options) = options.0.get(Target::Field(index)) else {
+                continue;
+            };
+            let (min, max) =
+                if let Some(number) = field_options.downcast_ref::<NumberOptions<f32>>() {
+                    (number.min, number.max)
+                } else if let Some(number) = field_options.downcast_ref::<NumberOptions<u32>>() {
+                    (
+                        number.min.map(|min| min as f32),
+                        number.max.map(|max| max as f32),
+                    )
+                } else {
+                    continue;
+                };
+            let name = field.name();
+            let range = EdgeDetection::field_range(name)
+                .unwrap_or_else(|| panic!("{name} has inspector bounds but no range"));
+            assert_eq!(min, Some(range.slider_min), "{name} inspector min");
+            if let Some(max) = max {
+                assert_eq!(max, range.slider_max, "{name} inspector max");
+            }
+        }
+    }
+}
@@ -4,12 +4,15 @@ use bevy::{
     core_pipeline::{
         Core3d, Core3dSystems, FullscreenShader,
         core_3d::DEPTH_PREPASS_TEXTURE_SUPPORTED,
-        prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
+        prepass::{DepthPrepass, MotionVectorPrepass, NormalPrepass, ViewPrepassTextures},
         tonemapping::tonemapping,
     },
+    diagnostic::FrameCount,
+    ecs::entity::EntityHashSet,
     prelude::*,
     render::{
         Extract, Render, RenderApp, RenderSystems,
+        camera::TemporalJitter,
         extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
         render_asset::RenderAssets,
         render_resource::{
@@ -21,11 +24,13 @@ use bevy::{
         },
         renderer::{RenderContext, RenderDevice, ViewQuery},
         sync_component::{SyncComponent, SyncComponentPlugin},
-        sync_world::RenderEntity,
-        texture::GpuImage,
+        sync_world::{MainEntity, RenderEntity},
+        texture::{CachedTexture, GpuImage, TextureCache},
         view::{ExtractedView, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
     },
 };
+#[cfg(feature = "inspector")]
+use bevy_inspector_egui::{InspectorOptions, prelude::ReflectInspectorOptions};
 
 /// Alpha-channel encoding for per-entity edge mask control.
 /// Set the normal prepass alpha to one of these values to control
@@ -41,64 +46,277 @@ pub mod edge_mask {
     pub const BOTH: f32 = 1.0;
 }
 
+mod builder;
+mod calibration;
+mod core_2d;
+mod depth_merge;
+mod depth_prefilter;
+mod diagnostics;
+mod field_ranges;
+mod overlay;
+mod overrides;
+mod presets;
+mod resolution_scale;
+mod stats;
+mod temporal;
+
+pub use calibration::{AUTO_CALIBRATE_DEPTH_FRACTION, AutoCalibrateEdgeDetection};
+pub use core_2d::EdgeDetection2dPlugin;
+pub use depth_merge::{EdgeDetectionDepthMerge, ViewEdgeMergedDepth};
+pub use depth_prefilter::{
+    EDGE_DEPTH_PYRAMID_TEXTURE_FORMAT, MAX_DEPTH_PREFILTER, ViewEdgeDepthPyramid,
+};
+pub use diagnostics::{
+    EDGE_DETECTION_GPU_TIME, EDGE_DETECTION_PIPELINE_VARIANTS, EdgeDetectionPipelineVariants,
+    PIPELINE_VARIANT_WARN_THRESHOLD,
+};
+pub use field_ranges::EdgeDetectionFieldRange;
+pub use overlay::{
+    EdgeDetectionOverlay, EdgeDetectionOverlayCamera, SELECTION_HIGHLIGHT_LAYER,
+    SelectionHighlight, XRAY_OUTLINE_LAYER, XRayOutline,
+};
+pub use overrides::{
+    EdgeDetectionOverride, EdgeDetectionOverrides, apply_edge_detection_overrides,
+};
+pub use presets::EdgeDetectionPreset;
+pub use resolution_scale::{EDGE_MASK_TEXTURE_FORMAT, ViewEdgeMaskTexture};
+pub use stats::{EdgeDetectionPassLog, EdgeDetectionReady, EdgeDetectionStats};
+pub use temporal::{EDGE_HISTORY_TEXTURE_FORMAT, ViewEdgeHistoryTextures};
+
 // ──────────────────────────────────────────────
 //  Plugin Setup
 // ──────────────────────────────────────────────
 #[derive(Default)]
-pub struct EdgeDetectionPlugin;
+pub struct EdgeDetectionPlugin {
+    /// Run the effect as if every camera were LDR, even HDR ones.
+    ///
+    /// The pass already runs after tonemapping, but on an HDR camera the view target
+    /// stays a float format and out-of-range values survive into the outline composite
+    /// (edge colors, color-edge gradients, later passes like bloom). Since the color
+    /// attachment must match the view target's format, LDR is emulated: on HDR views the
+    /// pipeline is keyed with `force_ldr`, which clamps the sampled scene color and the
+    /// composited output to [0, 1] — exactly what an LDR target would store.
+    ///
+    /// This gives crisp, fully black outlines on HDR cameras at the cost of HDR fidelity:
+    /// color edges can no longer be detected between values above 1.0, and outlined
+    /// pixels no longer carry over-bright values into bloom.
+    pub force_ldr: bool,
+    /// Where the pass runs in the `Core3d` post-process chain. See
+    /// [`EdgeDetectionPassOrder`].
+    pub pass_order: EdgeDetectionPassOrder,
+    /// Clamp [`EdgeDetection`] fields to their documented ranges whenever they
+    /// change, logging a warning the first time an entity needs it. See
+    /// [`clamp_edge_detection_settings`].
+    pub validate: bool,
+    /// Extra shader defs appended to every edge pipeline variant, to switch on the
+    /// shader's user hooks (`USER_EDGE_POSTPROCESS`, `USER_EDGE_COLOR`) or defs read
+    /// by an [`ActiveEdgeShader`]. See the "User shader hooks" section of the README.
+    pub shader_defs: Vec<ShaderDefVal>,
+    /// Asset path of a shader to use instead of the embedded one, loaded at startup
+    /// into an [`ActiveEdgeShader`]. With Bevy's `file_watcher` feature, saving the
+    /// file recompiles the pipelines, so the WGSL can be iterated on without
+    /// rebuilding the crate. Start from a copy of `edge_detection_shader.wgsl`.
+    pub shader_override: Option<String>,
+}
+
+/// Position of the edge pass relative to tonemapping, for [`EdgeDetectionPlugin`].
+///
+/// Both orders stay before FXAA/SMAA so anti-aliasing smooths the edges. For
+/// anything finer (e.g. relative to your own passes), order against
+/// [`EdgeDetectionPassSet`] with `configure_sets`; contradictory constraints are
+/// reported by the scheduler as a cycle when the schedule is built.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum EdgeDetectionPassOrder {
+    /// After tonemapping: edges are drawn on display colors, so `edge_color` is
+    /// exactly the color on screen and lines stay crisp. Bloom has already run and
+    /// doesn't pick up the edges.
+    #[default]
+    AfterTonemapping,
+    /// Before tonemapping, on the HDR scene: edge colors are tonemapped along with
+    /// the scene (an HDR `edge_color` can glow, or blend naturally into the
+    /// image), at the cost of edges no longer matching `edge_color` exactly. Color
+    /// edges are detected on HDR values, so `color_threshold` may need retuning.
+    /// Combine with `force_ldr` only if you want the HDR scene clamped.
+    ///
+    /// Only HDR cameras tonemap the edges: on LDR cameras Bevy tonemaps inside the
+    /// material shaders and the tonemapping pass does nothing, so `edge_color` is
+    /// written untonemapped, as with `AfterTonemapping`. An edge color therefore
+    /// looks different on LDR and HDR cameras in this order only. Other HDR
+    /// post-process passes such as bloom have no defined order against the pass;
+    /// order them against [`EdgeDetectionPassSet`] if it matters.
+    BeforeTonemapping,
+    /// Not scheduled: everything else (extraction, pipelines, textures) is set up
+    /// as usual, and the [`edge_detection`] pass system is left to you, for custom
+    /// `Core3d` orderings or your own render schedule:
+    ///
+    /// ```ignore
+    /// render_app.add_systems(
+    ///     Core3d,
+    ///     edge_detection
+    ///         .after(my_pass)
+    ///         .in_set(Core3dSystems::PostProcess)
+    ///         .in_set(EdgeDetectionPassSet),
+    /// );
+    /// ```
+    ///
+    /// The pass reads the prepass textures and writes the view target with
+    /// `post_process_write`, so it must run after the main passes. Only affects
+    /// `Core3d`; [`EdgeDetection2dPlugin`] always schedules the pass in `Core2d`.
+    Manual,
+}
+
+/// What the current platform supports, inserted by [`EdgeDetectionPlugin`]. Query it
+/// to tell users why outlines are missing, or to fall back to color-only settings.
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct EdgeDetectionSupported {
+    /// Whether the depth prepass can be sampled (`false` e.g. on WebGL2). When
+    /// `false`, cameras whose settings read the depth/normal prepasses (see
+    /// [`EdgeDetection::needs_prepass`]) draw no edges; color-only settings and 2D
+    /// cameras still work.
+    pub depth_prepass: bool,
+}
+
+/// Render-world copy of the [`EdgeDetectionPlugin`] options.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct EdgeDetectionPluginSettings {
+    pub force_ldr: bool,
+    pub shader_defs: Vec<ShaderDefVal>,
+}
 
 impl Plugin for EdgeDetectionPlugin {
     fn build(&self, app: &mut App) {
         embedded_asset!(app, "edge_detection_shader.wgsl");
         embedded_asset!(app, "perlin_noise.png");
 
-        app.register_type::<EdgeDetection>();
+        app.register_type::<EdgeDetection>()
+            .register_type::<HighContrastOutlines>()
+            .register_type::<EdgeColorFromNormal>()
+            .register_type::<EdgeDetectionNoise>()
+            .register_type::<EdgeDetectionRegionMask>()
+            .register_type::<EdgeDetectionImportance>()
+            .register_type::<EdgeDetectionSupported>()
+            .init_resource::<HighContrastOutlines>()
+            .insert_resource(EdgeDetectionSupported {
+                depth_prepass: DEPTH_PREPASS_TEXTURE_SUPPORTED,
+            })
+            .add_systems(
+                PostUpdate,
+                (
+                    validate_edge_detection_cameras
+                        .run_if(not(resource_exists::<core_2d::EdgeDetection2dSupport>)),
+                    insert_edge_detection_prepasses,
+                ),
+            );
+        if self.validate {
+            app.add_systems(
+                PostUpdate,
+                clamp_edge_detection_settings.before(insert_edge_detection_prepasses),
+            );
+        }
         app.add_plugins((
             SyncComponentPlugin::<EdgeDetection>::default(),
             UniformComponentPlugin::<EdgeDetectionUniform>::default(),
+            overlay::EdgeDetectionOverlayPlugin,
+            overrides::EdgeDetectionOverridesPlugin,
+            calibration::EdgeDetectionCalibrationPlugin,
+            resolution_scale::EdgeDetectionResolutionScalePlugin,
+            temporal::EdgeDetectionTemporalPlugin,
+            depth_prefilter::EdgeDetectionDepthPrefilterPlugin,
+            depth_merge::EdgeDetectionDepthMergePlugin,
+            diagnostics::EdgeDetectionDiagnosticsPlugin,
+            stats::EdgeDetectionStatsPlugin,
         ));
         // We need to get the render app from the main app
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
         };
         render_app
+            .insert_resource(EdgeDetectionPluginSettings {
+                force_ldr: self.force_ldr,
+                shader_defs: self.shader_defs.clone(),
+            })
             .init_resource::<SpecializedRenderPipelines<EdgeDetectionPipeline>>()
             .add_systems(
                 ExtractSchedule,
-                EdgeDetectionUniform::extract_edge_detection_settings,
+                (
+                    EdgeDetectionUniform::extract_edge_detection_settings,
+                    extract_active_edge_shader,
+                ),
             )
-            .add_systems(
+            .configure_sets(
                 Render,
-                prepare_edge_detection_pipelines.in_set(RenderSystems::Prepare),
+                EdgeDetectionSystems::PreparePipelines.in_set(RenderSystems::Prepare),
             )
-            // Render passes are plain systems in 0.19. Run after tonemapping (so we
-            // operate on tonemapped color) and before the anti-aliasing passes (so
-            // FXAA/SMAA smooth the detected edges), within the post-process stage.
             .add_systems(
-                Core3d,
-                edge_detection
-                    .after(tonemapping)
-                    .before(fxaa)
-                    .before(smaa)
-                    .in_set(Core3dSystems::PostProcess)
-                    .in_set(EdgeDetectionPassSet),
+                Render,
+                (
+                    prepare_edge_detection_pipelines.in_set(EdgeDetectionSystems::PreparePipelines),
+                    prepare_edge_direction_textures.in_set(RenderSystems::PrepareResources),
+                    prepare_edge_detection_masks.in_set(RenderSystems::PrepareResources),
+                    (
+                        scale_edge_detection_thickness,
+                        unjitter_edge_detection_prepass,
+                    )
+                        .in_set(RenderSystems::Prepare)
+                        .before(RenderSystems::PrepareResources),
+                ),
             );
+        // Render passes are plain systems in 0.19. By default run after
+        // tonemapping (so we operate on tonemapped color); always before the
+        // anti-aliasing passes (so FXAA/SMAA smooth the detected edges), within
+        // the post-process stage.
+        let pass = match self.pass_order {
+            EdgeDetectionPassOrder::AfterTonemapping => edge_detection.after(tonemapping),
+            EdgeDetectionPassOrder::BeforeTonemapping => edge_detection.before(tonemapping),
+            EdgeDetectionPassOrder::Manual => return,
+        };
+        render_app.add_systems(
+            Core3d,
+            pass.before(fxaa)
+                .before(smaa)
+                .in_set(Core3dSystems::PostProcess)
+                .in_set(EdgeDetectionPassSet),
+        );
     }
 
     fn finish(&self, app: &mut App) {
+        // In `finish` so the `AssetPlugin` is there regardless of plugin order.
+        if let Some(path) = &self.shader_override {
+            let shader = app.world().resource::<AssetServer>().load(path.clone());
+            app.insert_resource(ActiveEdgeShader(shader));
+        }
+        if !DEPTH_PREPASS_TEXTURE_SUPPORTED {
+            warn!(
+                "Edge detection needs depth texture sampling, which this backend doesn't \
+                 support: depth/normal edges are disabled and only color-only EdgeDetection \
+                 settings draw outlines. See EdgeDetectionSupported."
+            );
+        }
         app.sub_app_mut(RenderApp)
             .init_resource::<EdgeDetectionPipeline>();
     }
 }
 
 /// Public [`SystemSet`] containing the edge-detection render pass in the
-/// [`Core3d`] schedule. Another post-process pass that writes the same
+/// [`Core3d`] schedule (and in `Core2d` with [`EdgeDetection2dPlugin`]). Another post-process pass that writes the same
 /// `ViewTarget` on the same camera (e.g. a compositor) has no inherent order
 /// against this pass — the scheduler picks arbitrarily. Integrators must
 /// `configure_sets` an explicit before/after relation against this set.
 #[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EdgeDetectionPassSet;
 
+/// Public [`SystemSet`]s of the plugin's systems in the [`Render`] schedule, to
+/// order downstream systems against.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeDetectionSystems {
+    /// Specializes the edge detection (and edge composite) pipelines from each
+    /// view's [`EdgeDetection`] and key, in [`RenderSystems::Prepare`]. Systems that
+    /// change the render-world `EdgeDetection` of a view must run before it to
+    /// take effect in the same frame.
+    PreparePipelines,
+}
+
 // The render entity keeps a synced `EdgeDetection`, its derived
 // `EdgeDetectionUniform`, and the per-view derived `EdgeDetectionPipelineId` /
 // `DynamicUniformIndex`. ALL must be dropped when `EdgeDetection` is removed
@@ -111,91 +329,274 @@ impl SyncComponent for EdgeDetection {
         EdgeDetectionUniform,
         EdgeDetectionPipelineId,
         DynamicUniformIndex<EdgeDetectionUniform>,
+        ViewEdgeDirectionTexture,
+        EdgeDetectionMask,
+        EdgeDetectionOverride,
+        EdgeColorFromNormal,
+        EdgeDetectionNoise,
+        EdgeDetectionRegionMask,
+        EdgeDetectionImportance,
+        (
+            ViewEdgeMaskTexture,
+            resolution_scale::EdgeCompositePipelineId,
+        ),
+        ViewEdgeHistoryTextures,
+        (
+            ViewEdgeDepthPyramid,
+            depth_prefilter::EdgeDepthPrefilterPipelineIds,
+        ),
+        (ViewEdgeMergedDepth, depth_merge::EdgeDepthMergeSources),
     );
 }
 
 // This contains global data used by the render pipeline. This will be created once on startup.
 #[derive(Resource)]
 pub struct EdgeDetectionPipeline {
+    /// Shader the pipelines are specialized from: [`ActiveEdgeShader`] if present,
+    /// otherwise `embedded_shader`.
     pub shader: Handle<Shader>,
+    pub embedded_shader: Handle<Shader>,
     pub noise_texture: Handle<Image>,
     pub linear_sampler: Sampler,
     pub nonfiltering_sampler: Sampler,
     pub noise_sampler: Sampler,
-    pub layout_with_msaa: BindGroupLayoutDescriptor,
-    pub layout_without_msaa: BindGroupLayoutDescriptor,
     pub fullscreen_shader: FullscreenShader,
+    /// [`EdgeDetectionPlugin::shader_defs`], appended to every variant's defs.
+    pub user_shader_defs: Vec<ShaderDefVal>,
 }
 
 impl EdgeDetectionPipeline {
-    pub fn bind_group_layout(&self, multisampled: bool) -> &BindGroupLayoutDescriptor {
-        if multisampled {
-            &self.layout_with_msaa
-        } else {
-            &self.layout_without_msaa
+    /// Bind group layout for a pipeline variant. Binding indices match the `@binding`
+    /// numbers in the shader; bindings a variant doesn't use are left out.
+    pub fn bind_group_layout(&self, key: EdgeDetectionLayoutKey) -> BindGroupLayoutDescriptor {
+        let stages = ShaderStages::FRAGMENT;
+        let mut entries = vec![];
+        if key.color_source {
+            // color attachment
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: true }).build(0, stages));
+        }
+        if !key.color_only {
+            // depth prepass
+            entries.push(if key.multisampled {
+                texture_depth_2d_multisampled().build(1, stages)
+            } else {
+                texture_depth_2d().build(1, stages)
+            });
+            if !key.normals_from_depth {
+                // normal prepass
+                entries.push(if key.multisampled {
+                    texture_2d_multisampled(TextureSampleType::Float { filterable: false })
+                        .build(2, stages)
+                } else {
+                    texture_2d(TextureSampleType::Float { filterable: true }).build(2, stages)
+                });
+            }
+        }
+        entries.extend([
+            // filtering sampler for color/normal
+            sampler(SamplerBindingType::Filtering).build(3, stages),
+            // non-filtering sampler for depth prepass
+            sampler(SamplerBindingType::NonFiltering).build(4, stages),
+            // perlin-noise texture
+            texture_2d(TextureSampleType::Float { filterable: true }).build(5, stages),
+            // perlin-noise sampler
+            sampler(SamplerBindingType::Filtering).build(6, stages),
+            // view
+            uniform_buffer::<ViewUniform>(true).build(7, stages),
+            // The uniform that will control the effect
+            uniform_buffer::<EdgeDetectionUniform>(true).build(8, stages),
+        ]);
+        if key.motion_gate || key.temporal {
+            // motion vector prepass
+            entries.push(if key.multisampled {
+                texture_2d_multisampled(TextureSampleType::Float { filterable: false })
+                    .build(10, stages)
+            } else {
+                texture_2d(TextureSampleType::Float { filterable: false }).build(10, stages)
+            });
+        }
+        if key.edge_color_from_normal {
+            // matcap-style edge color lookup
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: true }).build(9, stages));
+        }
+        if key.region_mask {
+            // user-supplied region mask
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: true }).build(11, stages));
+        }
+        if key.temporal {
+            // last frame's edge strength
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: true }).build(12, stages));
+        }
+        if key.importance {
+            // user-supplied threshold weights
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: true }).build(13, stages));
+        }
+        if key.depth_prefilter {
+            // downsampled linear depth
+            entries
+                .push(texture_2d(TextureSampleType::Float { filterable: false }).build(14, stages));
+        }
+
+        BindGroupLayoutDescriptor::new("edge_detection: bind_group_layout", &entries)
+    }
+
+    /// Creates the bind group for a pipeline variant, matching
+    /// [`bind_group_layout`](Self::bind_group_layout) for the same `key`. The
+    /// optional `inputs` must be present exactly when `key` binds them.
+    pub fn create_bind_group(
+        &self,
+        render_device: &RenderDevice,
+        pipeline_cache: &PipelineCache,
+        key: EdgeDetectionLayoutKey,
+        inputs: &EdgeDetectionBindGroupInputs,
+    ) -> BindGroup {
+        let mut entries = vec![];
+        if key.color_source {
+            // color attachment
+            entries.push(BindGroupEntry {
+                binding: 0,
+                resource: inputs.source.into_binding(),
+            });
+        }
+        if let Some(depth) = inputs.depth {
+            // depth prepass
+            entries.push(BindGroupEntry {
+                binding: 1,
+                resource: depth.into_binding(),
+            });
+        }
+        if let Some(normal) = inputs.normal {
+            // normal prepass
+            entries.push(BindGroupEntry {
+                binding: 2,
+                resource: normal.into_binding(),
+            });
+        }
+        entries.extend([
+            // filtering sampler for color/normal (nearest for `nearest_filtering`)
+            BindGroupEntry {
+                binding: 3,
+                resource: if key.nearest_filtering {
+                    self.nonfiltering_sampler.into_binding()
+                } else {
+                    self.linear_sampler.into_binding()
+                },
+            },
+            // non-filtering sampler for depth prepass
+            BindGroupEntry {
+                binding: 4,
+                resource: self.nonfiltering_sampler.into_binding(),
+            },
+            // perlin-noise texture
+            BindGroupEntry {
+                binding: 5,
+                resource: inputs.noise.into_binding(),
+            },
+            // perlin-noise sampler
+            BindGroupEntry {
+                binding: 6,
+                resource: self.noise_sampler.into_binding(),
+            },
+            // view
+            BindGroupEntry {
+                binding: 7,
+                resource: inputs.view_uniforms.clone(),
+            },
+            // The uniform that will control the effect
+            BindGroupEntry {
+                binding: 8,
+                resource: inputs.edge_detection_uniforms.clone(),
+            },
+        ]);
+        if let Some(motion_vectors) = inputs.motion_vectors {
+            // motion vector prepass
+            entries.push(BindGroupEntry {
+                binding: 10,
+                resource: motion_vectors.into_binding(),
+            });
+        }
+        if let Some(normal_color) = inputs.normal_color {
+            // matcap-style edge color lookup
+            entries.push(BindGroupEntry {
+                binding: 9,
+                resource: normal_color.into_binding(),
+            });
+        }
+        if let Some(region_mask) = inputs.region_mask {
+            // user-supplied region mask
+            entries.push(BindGroupEntry {
+                binding: 11,
+                resource: region_mask.into_binding(),
+            });
         }
+        if let Some(history) = inputs.history {
+            // last frame's edge strength
+            entries.push(BindGroupEntry {
+                binding: 12,
+                resource: history.into_binding(),
+            });
+        }
+        if let Some(importance) = inputs.importance {
+            // user-supplied threshold weights
+            entries.push(BindGroupEntry {
+                binding: 13,
+                resource: importance.into_binding(),
+            });
+        }
+        if let Some(depth_pyramid) = inputs.depth_pyramid {
+            // downsampled linear depth
+            entries.push(BindGroupEntry {
+                binding: 14,
+                resource: depth_pyramid.into_binding(),
+            });
+        }
+
+        render_device.create_bind_group(
+            "edge_detection_bind_group",
+            &pipeline_cache.get_bind_group_layout(&self.bind_group_layout(key)),
+            &entries,
+        )
     }
 }
 
+/// Everything [`EdgeDetectionPipeline::create_bind_group`] binds besides the
+/// pipeline's own samplers, for reusing the layout in derived effects.
+pub struct EdgeDetectionBindGroupInputs<'a> {
+    /// Color input, bound when the key has `color_source` (usually the
+    /// `post_process_write` source).
+    pub source: &'a TextureView,
+    /// Depth prepass; `None` for color-only keys.
+    pub depth: Option<&'a TextureView>,
+    /// Normal prepass; `None` for color-only keys and normals reconstructed from depth.
+    pub normal: Option<&'a TextureView>,
+    /// Distortion noise texture.
+    pub noise: &'a TextureView,
+    /// Motion vector prepass, for keys with `motion_gate` or `temporal`.
+    pub motion_vectors: Option<&'a TextureView>,
+    /// [`EdgeColorFromNormal`] lookup, for keys with `edge_color_from_normal`.
+    pub normal_color: Option<&'a TextureView>,
+    /// [`EdgeDetectionRegionMask`] texture, for keys with `region_mask`.
+    pub region_mask: Option<&'a TextureView>,
+    /// Last frame's [`ViewEdgeHistoryTextures`] output, for keys with `temporal`.
+    pub history: Option<&'a TextureView>,
+    /// [`EdgeDetectionImportance`] texture, for keys with `importance`.
+    pub importance: Option<&'a TextureView>,
+    /// Sampled level of the [`ViewEdgeDepthPyramid`], for keys with `depth_prefilter`.
+    pub depth_pyramid: Option<&'a TextureView>,
+    pub view_uniforms: BindingResource<'a>,
+    pub edge_detection_uniforms: BindingResource<'a>,
+}
+
 impl FromWorld for EdgeDetectionPipeline {
     fn from_world(world: &mut World) -> Self {
         let shader = load_embedded_asset!(world, "edge_detection_shader.wgsl");
         let noise_texture = load_embedded_asset!(world, "perlin_noise.png");
 
-        let layout_with_msaa = BindGroupLayoutDescriptor::new(
-            "edge_detection: bind_group_layout with msaa",
-            &BindGroupLayoutEntries::sequential(
-                ShaderStages::FRAGMENT,
-                (
-                    // color attachment
-                    texture_2d(TextureSampleType::Float { filterable: true }),
-                    // depth prepass
-                    texture_depth_2d_multisampled(),
-                    // normal prepass
-                    texture_2d_multisampled(TextureSampleType::Float { filterable: false }),
-                    // filtering sampler for color/normal
-                    sampler(SamplerBindingType::Filtering),
-                    // non-filtering sampler for depth prepass
-                    sampler(SamplerBindingType::NonFiltering),
-                    // perlin-noise texture
-                    texture_2d(TextureSampleType::Float { filterable: true }),
-                    // perlin-noise sampler
-                    sampler(SamplerBindingType::Filtering),
-                    // view
-                    uniform_buffer::<ViewUniform>(true),
-                    // The uniform that will control the effect
-                    uniform_buffer::<EdgeDetectionUniform>(true),
-                ),
-            ),
-        );
-
-        let layout_without_msaa = BindGroupLayoutDescriptor::new(
-            "edge_detection: bind_group_layout without msaa",
-            &BindGroupLayoutEntries::sequential(
-                ShaderStages::FRAGMENT,
-                (
-                    // color attachment
-                    texture_2d(TextureSampleType::Float { filterable: true }),
-                    // depth prepass
-                    texture_depth_2d(),
-                    // normal prepass
-                    texture_2d(TextureSampleType::Float { filterable: true }),
-                    // texture sampler
-                    sampler(SamplerBindingType::Filtering),
-                    // for depth
-                    sampler(SamplerBindingType::NonFiltering),
-                    // perlin-noise texture
-                    texture_2d(TextureSampleType::Float { filterable: true }),
-                    // perlin-noise sampler
-                    sampler(SamplerBindingType::Filtering),
-                    // view
-                    uniform_buffer::<ViewUniform>(true),
-                    // The uniform that will control the effect
-                    uniform_buffer::<EdgeDetectionUniform>(true),
-                ),
-            ),
-        );
-
         let render_device = world.resource::<RenderDevice>();
 
         let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
@@ -221,14 +622,17 @@ impl FromWorld for EdgeDetectionPipeline {
         });
 
         Self {
-            shader,
+            shader: shader.clone(),
+            embedded_shader: shader,
             noise_texture,
             linear_sampler,
             nonfiltering_sampler,
             noise_sampler,
-            layout_with_msaa,
-            layout_without_msaa,
             fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+            user_shader_defs: world
+                .get_resource::<EdgeDetectionPluginSettings>()
+                .map(|settings| settings.shader_defs.clone())
+                .unwrap_or_default(),
         }
     }
 }
@@ -237,11 +641,39 @@ impl SpecializedRenderPipeline for EdgeDetectionPipeline {
     type Key = EdgeDetectionKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
-        let targets = vec![Some(ColorTargetState {
+        // No fixed-function blending: `blend_mode` is applied in the shader (see
+        // [`EdgeBlendMode`]).
+        let mut targets = vec![Some(ColorTargetState {
             format: key.target_format,
             blend: None,
             write_mask: ColorWrites::ALL,
         })];
+        if key.edge_direction_target {
+            targets.push(Some(ColorTargetState {
+                format: EDGE_DIRECTION_TEXTURE_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }));
+        }
+        // The mask is always location 2, with an empty slot when there is no
+        // direction target.
+        if key.edge_mask_target {
+            targets.resize(2, None);
+            targets.push(Some(ColorTargetState {
+                format: EDGE_DETECTION_MASK_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }));
+        }
+        // The edge history is location 3.
+        if key.temporal {
+            targets.resize(3, None);
+            targets.push(Some(ColorTargetState {
+                format: EDGE_HISTORY_TEXTURE_FORMAT,
+                blend: None,
+                write_mask: ColorWrites::ALL,
+            }));
+        }
 
         let mut shader_defs = vec![];
 
@@ -253,29 +685,221 @@ impl SpecializedRenderPipeline for EdgeDetectionPipeline {
             shader_defs.push("ENABLE_NORMAL".into());
         }
 
+        if key.enable_crease {
+            shader_defs.push("ENABLE_CREASE".into());
+        }
+
         if key.enable_color {
             shader_defs.push("ENABLE_COLOR".into());
         }
 
+        if key.enable_custom {
+            shader_defs.push("CUSTOM_EDGE_SOURCE".into());
+        }
+
         match key.operator {
             EdgeOperator::Sobel => shader_defs.push("OPERATOR_SOBEL".into()),
             EdgeOperator::RobertsCross => shader_defs.push("OPERATOR_ROBERTS_CROSS".into()),
             EdgeOperator::PixelArt => shader_defs.push("OPERATOR_PIXEL_ART".into()),
+            // textureGather can't read multisampled textures; MSAA views use the
+            // regular 4-sample Roberts Cross instead. Same without a normal texture
+            // to gather from, or when its channels need decoding first.
+            EdgeOperator::Roberts
+                if key.multisampled
+                    || key.normals_from_depth()
+                    || key.normal_encoding == NormalEncoding::Octahedral =>
+            {
+                shader_defs.push("OPERATOR_ROBERTS_CROSS".into())
+            }
+            EdgeOperator::Roberts => shader_defs.push("OPERATOR_ROBERTS_GATHER".into()),
+        }
+
+        // The kernel only shapes the 3x3 operator; other operators share one variant.
+        if key.operator == EdgeOperator::Sobel {
+            match key.kernel {
+                EdgeKernel::Sobel => {}
+                EdgeKernel::Scharr => shader_defs.push("KERNEL_SCHARR".into()),
+                EdgeKernel::Prewitt => shader_defs.push("KERNEL_PREWITT".into()),
+                EdgeKernel::Laplacian => shader_defs.push("KERNEL_LAPLACIAN".into()),
+            }
+            if key.kernel_radius > 1 && key.kernel != EdgeKernel::Laplacian {
+                shader_defs.push(ShaderDefVal::UInt(
+                    "KERNEL_RADIUS".into(),
+                    key.kernel_radius,
+                ));
+            }
+        }
+
+        match key.color_edge_space {
+            ColorEdgeSpace::Rgb => {}
+            ColorEdgeSpace::Luminance => shader_defs.push("COLOR_EDGE_LUMINANCE".into()),
+            ColorEdgeSpace::Oklab => shader_defs.push("COLOR_EDGE_OKLAB".into()),
+        }
+
+        if key.skip_transparent && key.enable_color && !key.color_only {
+            shader_defs.push("SKIP_TRANSPARENT".into());
+        }
+
+        if key.depth_edge_mode == DepthEdgeMode::MaxNeighborDelta {
+            shader_defs.push("DEPTH_EDGE_MAX_NEIGHBOR_DELTA".into());
+        }
+
+        if key.depth_prefilter {
+            shader_defs.push("DEPTH_PREFILTER".into());
         }
 
         if key.multisampled {
             shader_defs.push("MULTISAMPLED".into());
         }
 
+        if key.color_only {
+            shader_defs.push("COLOR_ONLY".into());
+        }
+
+        if key.force_ldr {
+            shader_defs.push("FORCE_LDR".into());
+        }
+
+        if key.hdr_edges {
+            shader_defs.push("EDGE_EMISSIVE".into());
+        }
+
+        if key.unjitter_prepass {
+            shader_defs.push("UNJITTER_PREPASS".into());
+        }
+
+        if !key.color_source {
+            shader_defs.push("NO_COLOR_SOURCE".into());
+        }
+
+        if key.flat_background {
+            shader_defs.push("FLAT_BACKGROUND".into());
+        }
+
+        if key.silhouette_halo {
+            shader_defs.push("SILHOUETTE_HALO".into());
+        }
+
+        if key.outline_against_background && !key.color_only {
+            shader_defs.push("OUTLINE_AGAINST_BACKGROUND".into());
+        }
+
+        if key.coverage_thickness {
+            shader_defs.push("COVERAGE_THICKNESS".into());
+        }
+
+        if key.edge_color_from_normal {
+            shader_defs.push("EDGE_COLOR_FROM_NORMAL".into());
+            if key.normal_space == NormalSpace::World {
+                shader_defs.push("NORMAL_SPACE_WORLD".into());
+            }
+        }
+
+        if key.edge_color_mode == EdgeColorMode::Direction {
+            shader_defs.push("EDGE_COLOR_BY_DIRECTION".into());
+        }
+
+        match key.blend_mode {
+            EdgeBlendMode::Replace => {}
+            EdgeBlendMode::Alpha => shader_defs.push("EDGE_BLEND_ALPHA".into()),
+            EdgeBlendMode::Additive => shader_defs.push("EDGE_BLEND_ADDITIVE".into()),
+        }
+
+        if key.motion_gate || key.temporal {
+            shader_defs.push("MOTION_VECTORS".into());
+        }
+
+        if key.motion_gate {
+            shader_defs.push("MOTION_GATE".into());
+        }
+
+        if key.temporal {
+            shader_defs.push("TEMPORAL_HISTORY".into());
+        }
+
+        if key.importance {
+            shader_defs.push("EDGE_IMPORTANCE".into());
+        }
+
+        if key.region_mask {
+            shader_defs.push("REGION_MASK".into());
+        }
+
+        if key.multisampled && key.normal_resolve != NormalResolve::Sample {
+            shader_defs.push("DEPTH_RESOLVE_AVERAGE".into());
+        }
+
+        if key.normals_from_depth() {
+            shader_defs.push("NORMALS_FROM_DEPTH".into());
+        } else if !key.color_only && key.normal_encoding == NormalEncoding::Octahedral {
+            shader_defs.push("NORMAL_ENCODING_OCTAHEDRAL".into());
+        }
+        if !key.normals_from_depth() && key.multisampled {
+            match key.normal_resolve {
+                NormalResolve::Average => shader_defs.push("NORMAL_RESOLVE_AVERAGE".into()),
+                NormalResolve::Sample => {}
+                NormalResolve::Dominant => shader_defs.push("NORMAL_RESOLVE_DOMINANT".into()),
+            }
+        }
+
+        match key.border_outline {
+            BorderMode::None => {}
+            BorderMode::Suppress => shader_defs.push("BORDER_CLAMP".into()),
+            BorderMode::Draw => {
+                shader_defs.push("BORDER_CLAMP".into());
+                shader_defs.push("BORDER_DRAW".into());
+            }
+        }
+
+        // The edge mask (reduced resolution or dilation) is the
+        // transparent-background output, composited over the scene by a second pass.
+        if key.transparent_background || key.mask_composite {
+            shader_defs.push("TRANSPARENT_BACKGROUND".into());
+        }
+
+        if key.thin_feature_recovery {
+            shader_defs.push("THIN_FEATURE_RECOVERY".into());
+        }
+
+        if key.depth_normal_mode == DepthNormalMode::JointDepthNormal {
+            shader_defs.push("JOINT_DEPTH_NORMAL".into());
+        }
+
+        if key.edge_direction_target {
+            shader_defs.push("EDGE_DIRECTION_TARGET".into());
+        }
+
+        if key.edge_mask_target {
+            shader_defs.push("OUTPUT_MASK".into());
+        }
+
+        match key.debug_view {
+            DebugView::Off => {}
+            DebugView::EdgeDirection => shader_defs.push("DEBUG_EDGE_DIRECTION".into()),
+            DebugView::DistortionField => shader_defs.push("DEBUG_DISTORTION_FIELD".into()),
+            DebugView::ChannelColors => shader_defs.push("DEBUG_CHANNEL_COLORS".into()),
+            DebugView::EdgeStrengthHeatmap => {
+                shader_defs.push("DEBUG_EDGE_STRENGTH_HEATMAP".into());
+            }
+            // Color-only views have no normals to show.
+            DebugView::Normals if key.color_only => {}
+            DebugView::Normals => shader_defs.push("DEBUG_NORMALS".into()),
+        }
+
         match key.projection {
             ProjectionType::Perspective => shader_defs.push("VIEW_PROJECTION_PERSPECTIVE".into()),
             ProjectionType::Orthographic => shader_defs.push("VIEW_PROJECTION_ORTHOGRAPHIC".into()),
-            _ => (),
+            // Custom projections: no def, the shader unprojects through the view's
+            // inverse projection matrices.
+            ProjectionType::None => (),
         };
 
+        // EdgeDetectionPlugin::shader_defs
+        shader_defs.extend(self.user_shader_defs.iter().cloned());
+
         RenderPipelineDescriptor {
             label: Some("edge_detection: pipeline".into()),
-            layout: vec![self.bind_group_layout(key.multisampled).clone()],
+            layout: vec![self.bind_group_layout(key.layout_key())],
             vertex: self.fullscreen_shader.to_vertex_state(),
             fragment: Some(FragmentState {
                 shader: self.shader.clone(),
@@ -292,30 +916,266 @@ impl SpecializedRenderPipeline for EdgeDetectionPipeline {
     }
 }
 
+/// The specialized pipeline of an edge-detection view, with its layout key.
 #[derive(Component, Clone, Copy)]
-pub struct EdgeDetectionPipelineId(CachedRenderPipelineId);
+pub struct EdgeDetectionPipelineId(CachedRenderPipelineId, EdgeDetectionLayoutKey);
+
+impl EdgeDetectionPipelineId {
+    /// The cached pipeline the edge pass renders with.
+    pub fn id(&self) -> CachedRenderPipelineId {
+        self.0
+    }
+
+    /// Whether the pipeline has finished compiling. Views with a reduced resolution,
+    /// dilation or `depth_prefilter` also need their secondary pipelines; see
+    /// [`EdgeDetectionReady`] for the combined flag.
+    pub fn is_ready(&self, pipeline_cache: &PipelineCache) -> bool {
+        pipeline_cache.get_render_pipeline(self.0).is_some()
+    }
+}
 
+/// Specializes the pipeline for every edge-detection view.
+///
+/// Specialized pipelines are cached by [`EdgeDetectionKey`] in the
+/// [`SpecializedRenderPipelines`] resource, which outlives any camera. Removing
+/// `EdgeDetection` only drops the view's [`EdgeDetectionPipelineId`]; re-inserting
+/// settings with the same key (on the same or a rebuilt camera) gets the same
+/// cached pipeline id back without recompiling. In debug builds the
+/// [`EDGE_DETECTION_PIPELINE_VARIANTS`] diagnostic only counts new keys, so it
+/// stays flat across such swaps (checked by `tests/pipeline_reuse.rs`).
 pub fn prepare_edge_detection_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<EdgeDetectionPipeline>>,
     edge_detection_pipeline: Res<EdgeDetectionPipeline>,
+    plugin_settings: Res<EdgeDetectionPluginSettings>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    mut variants: Option<ResMut<EdgeDetectionPipelineVariants>>,
     query: Query<(
         Entity,
         &EdgeDetection,
         Option<&Projection>,
         &ExtractedView,
         &ViewTarget,
+        Has<DepthPrepass>,
+        Has<NormalPrepass>,
+        Has<MotionVectorPrepass>,
+        Option<&EdgeColorFromNormal>,
+        Option<&EdgeDetectionRegionMask>,
+        Option<&EdgeDetectionImportance>,
+        Has<TemporalJitter>,
+        Has<Camera2d>,
     )>,
 ) {
-    for (entity, edge_detection, projection, view, view_target) in &query {
+    for (
+        entity,
+        edge_detection,
+        projection,
+        view,
+        view_target,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        edge_color_from_normal,
+        region_mask,
+        importance,
+        jittered,
+        is_2d,
+    ) in &query
+    {
         let multisampled = view_target.sampled_main_texture_view().is_some();
-        let id = pipelines.specialize(
-            &pipeline_cache,
-            &edge_detection_pipeline,
-            EdgeDetectionKey::new(edge_detection, view.target_format, multisampled, projection),
+        let mut key = EdgeDetectionKey::new(
+            edge_detection,
+            view.target_format,
+            multisampled,
+            projection,
+            DEPTH_PREPASS_TEXTURE_SUPPORTED
+                && depth_prepass
+                && (normal_prepass
+                    || edge_detection.normal_source == NormalSource::ReconstructFromDepth),
+        );
+        if is_2d {
+            key = key.color_only_2d();
+        }
+        key.force_ldr = plugin_settings.force_ldr && view_target.is_hdr();
+        key.hdr_edges = view_target.is_hdr() && !key.force_ldr;
+        key.unjitter_prepass = jittered && !key.color_only;
+        // Needs the normal prepass, and the lookup image once it is on the GPU.
+        key.edge_color_from_normal = !key.color_only
+            && edge_color_from_normal.is_some_and(|lookup| gpu_images.contains(&lookup.0));
+        key.motion_gate =
+            edge_detection.enable_motion_gate && motion_vector_prepass && !key.color_only;
+        // Unmasked until the image is on the GPU.
+        key.region_mask = region_mask.is_some_and(|mask| gpu_images.contains(&mask.0));
+        // Unweighted until the image is on the GPU.
+        key.importance = importance.is_some_and(|weights| gpu_images.contains(&weights.0));
+        key.temporal =
+            temporal::is_temporal(edge_detection) && motion_vector_prepass && !key.color_only;
+        let id = pipelines.specialize(&pipeline_cache, &edge_detection_pipeline, key);
+        if let Some(variants) = variants.as_mut() {
+            variants.record(key);
+        }
+        commands
+            .entity(entity)
+            .insert(EdgeDetectionPipelineId(id, key.layout_key()));
+    }
+}
+
+/// Format of [`ViewEdgeDirectionTexture`].
+pub const EDGE_DIRECTION_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rg16Float;
+
+/// Screen-space edge direction written by the edge pass as a second render target
+/// for views with [`EdgeDetection::output_edge_direction`].
+///
+/// RG holds the normalized edge gradient encoded as `gradient.xy * 0.5 + 0.5`
+/// (UV space: +x right, +y down); non-edge pixels are `(0.5, 0.5)`. The gradient
+/// is taken from view depth, or from color luminance on color-only views. Bind
+/// it in a later post-process pass (e.g. to orient brush strokes along edges).
+#[derive(Component, Clone)]
+pub struct ViewEdgeDirectionTexture {
+    pub texture: CachedTexture,
+}
+
+/// Allocates a [`ViewEdgeDirectionTexture`] matching the main texture size for
+/// views with `output_edge_direction`, and drops it from views without.
+pub fn prepare_edge_direction_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(
+        Entity,
+        &EdgeDetection,
+        &ViewTarget,
+        Has<ViewEdgeDirectionTexture>,
+    )>,
+) {
+    for (entity, edge_detection, view_target, has_texture) in &query {
+        // Not available at reduced resolution: attachments must match in size.
+        if !edge_detection.output_edge_direction || resolution_scale::is_scaled(edge_detection) {
+            if has_texture {
+                commands.entity(entity).remove::<ViewEdgeDirectionTexture>();
+            }
+            continue;
+        }
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("edge_detection: edge direction texture"),
+                size: view_target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: EDGE_DIRECTION_TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewEdgeDirectionTexture { texture });
+    }
+}
+
+/// Format of [`EdgeDetectionMask`].
+pub const EDGE_DETECTION_MASK_FORMAT: TextureFormat = TextureFormat::R16Float;
+
+/// Raw edge strength written by the edge pass as an extra render target for views
+/// with [`EdgeDetection::output_edge_mask`].
+///
+/// R holds the final edge strength in `[0, 1]` (after thresholds, fading and
+/// `global_intensity`, before `edge_opacity` and the edge color); non-edge pixels
+/// are 0. Bind it in a later post-process pass, e.g. to drive a glow.
+#[derive(Component, Clone)]
+pub struct EdgeDetectionMask {
+    pub texture: CachedTexture,
+}
+
+/// Allocates an [`EdgeDetectionMask`] matching the main texture size for views with
+/// `output_edge_mask`, and drops it from views without.
+pub fn prepare_edge_detection_masks(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(Entity, &EdgeDetection, &ViewTarget, Has<EdgeDetectionMask>)>,
+) {
+    for (entity, edge_detection, view_target, has_texture) in &query {
+        // Not available at reduced resolution: attachments must match in size.
+        if !edge_detection.output_edge_mask || resolution_scale::is_scaled(edge_detection) {
+            if has_texture {
+                commands.entity(entity).remove::<EdgeDetectionMask>();
+            }
+            continue;
+        }
+
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("edge_detection: edge mask texture"),
+                size: view_target.main_texture().size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: EDGE_DETECTION_MASK_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
         );
-        commands.entity(entity).insert(EdgeDetectionPipelineId(id));
+        commands
+            .entity(entity)
+            .insert(EdgeDetectionMask { texture });
+    }
+}
+
+/// Render target height at which `resolution_independent_thickness` leaves
+/// thickness values unchanged.
+pub const THICKNESS_REFERENCE_HEIGHT: f32 = 1080.0;
+
+/// Scales the thickness uniforms by `viewport height / THICKNESS_REFERENCE_HEIGHT`
+/// for views with `resolution_independent_thickness`, and by `1 / resolution_scale`
+/// for reduced-resolution views. Runs before the uniform buffer is written; the
+/// uniform is re-extracted every frame, so this never compounds.
+pub fn scale_edge_detection_thickness(
+    mut query: Query<(&EdgeDetection, &ExtractedView, &mut EdgeDetectionUniform)>,
+) {
+    for (edge_detection, view, mut uniform) in &mut query {
+        scale_thickness(edge_detection, view.viewport.w, &mut uniform);
+    }
+}
+
+/// [`scale_edge_detection_thickness`] for one view rendering `viewport_height`
+/// pixels high.
+fn scale_thickness(
+    edge_detection: &EdgeDetection,
+    viewport_height: u32,
+    uniform: &mut EdgeDetectionUniform,
+) {
+    let mut scale = 1.0;
+    if edge_detection.resolution_independent_thickness {
+        scale *= viewport_height as f32 / THICKNESS_REFERENCE_HEIGHT;
+    }
+    if resolution_scale::is_scaled(edge_detection) {
+        scale /= resolution_scale::effective_scale(edge_detection);
+    }
+    if scale == 1.0 {
+        return;
+    }
+    uniform.depth_thickness *= scale;
+    uniform.normal_thickness *= scale;
+    uniform.color_thickness *= scale;
+    uniform.custom_thickness *= scale;
+}
+
+/// Sets `prepass_jitter` for views jittered by TAA ([`TemporalJitter`]). The edge
+/// pass runs after the TAA resolve, whose output is unjittered, while the prepass
+/// textures are rendered with this frame's sub-pixel jitter: shifting prepass reads
+/// by the jitter keeps depth and normal edges in place instead of crawling.
+pub fn unjitter_edge_detection_prepass(
+    mut query: Query<(&ExtractedView, &TemporalJitter, &mut EdgeDetectionUniform)>,
+) {
+    for (view, jitter, mut uniform) in &mut query {
+        // The jittered projection moves the image by `-offset` pixels.
+        uniform.prepass_jitter = -jitter.offset / view.viewport.zw().as_vec2();
     }
 }
 
@@ -323,6 +1183,115 @@ pub fn prepare_edge_detection_pipelines(
 //  Core structs and types
 // ──────────────────────────────────────────────
 
+/// A user-supplied WGSL module that adds a custom edge source.
+///
+/// The module is imported by the edge shader through Bevy's shader import system,
+/// so it must start with
+///
+/// ```wgsl
+/// #define_import_path bevy_edge_detection_outline::custom_edge_source
+/// ```
+///
+/// and define a function with exactly this signature:
+///
+/// ```wgsl
+/// fn custom_edge_value(uv: vec2f, view_z: f32, normal: vec4f, color: vec4f) -> f32
+/// ```
+///
+/// `view_z` is the linearized prepass depth (negative in front of the camera),
+/// `normal` the raw normal prepass texel (xyz packed to [0, 1], alpha = edge mask)
+/// and `color` the scene color at `uv`. The returned scalar is run through the
+/// active operator's gradient kernel and compared against `custom_threshold` on
+/// cameras with `enable_custom`. Without a prepass, `view_z` and `normal` are zero.
+///
+/// Insert this resource to keep the shader loaded:
+///
+/// ```ignore
+/// commands.insert_resource(EdgeDetectionCustomSource(asset_server.load("shaders/my_edge.wgsl")));
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct EdgeDetectionCustomSource(pub Handle<Shader>);
+
+/// Replaces the embedded edge shader at runtime, e.g. to A/B test a modified copy
+/// while iterating on detection algorithms. Insert, change or remove it at any time:
+/// the cached pipelines are dropped and every view re-specializes from the active
+/// shader (removing the resource switches back to the embedded one).
+///
+/// The shader must be bind-group compatible with the embedded one: the same
+/// `@group(0)` bindings per variant (including the ones left out under
+/// `COLOR_ONLY`/`NO_COLOR_SOURCE`), the same `EdgeDetectionUniform` struct layout,
+/// a `fragment` entry point, and the same shader defs handled. Starting from a copy
+/// of `edge_detection_shader.wgsl` is the easy way to get this right.
+#[derive(Resource, Clone, Debug)]
+pub struct ActiveEdgeShader(pub Handle<Shader>);
+
+/// Switches [`EdgeDetectionPipeline::shader`] to the [`ActiveEdgeShader`] (or back to
+/// the embedded shader) and clears the specialized pipeline cache when it changes.
+pub fn extract_active_edge_shader(
+    active: Extract<Option<Res<ActiveEdgeShader>>>,
+    pipeline: Option<ResMut<EdgeDetectionPipeline>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<EdgeDetectionPipeline>>,
+) {
+    let Some(mut pipeline) = pipeline else {
+        return;
+    };
+    let shader = active
+        .as_ref()
+        .map_or(&pipeline.embedded_shader, |active| &active.0)
+        .clone();
+    if shader == pipeline.shader {
+        return;
+    }
+    pipeline.shader = shader;
+    *pipelines = SpecializedRenderPipelines::default();
+}
+
+/// Matcap-style edge color: on a camera with [`EdgeDetection`], edges are colored by
+/// sampling this image with the edge pixel's view-space normal (`xy * 0.5 + 0.5`,
+/// y up, so the image center faces the camera), replacing the edge, silhouette and
+/// crease colors. Edge color alpha still applies. With [`NormalSpace::World`] the
+/// world-space normal is used instead.
+///
+/// Needs the normal prepass (ignored by color-only settings). This is a separate
+/// component because [`EdgeDetection`] is `Copy` and can't hold a handle.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct EdgeColorFromNormal(pub Handle<Image>);
+
+/// Replaces the embedded Perlin noise that drives the UV distortion on a camera
+/// with [`EdgeDetection`], for custom distortion patterns. Sampled like the
+/// embedded texture: RG in [0, 1], repeating, scaled by `uv_distortion_frequency`.
+/// Until the image is loaded the embedded noise is used.
+///
+/// A separate component because [`EdgeDetection`] is `Copy` and can't hold a handle.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct EdgeDetectionNoise(pub Handle<Image>);
+
+/// Restricts edges on a camera with [`EdgeDetection`] to a screen region: the
+/// image's red channel is sampled at each pixel (stretched over the viewport) and
+/// scales the edge strength, so edges are only drawn where it is non-zero. Render
+/// selected objects into it (e.g. with a second camera targeting the image) to
+/// outline only them. Edges are unmasked until the image is loaded.
+///
+/// A separate component because [`EdgeDetection`] is `Copy` and can't hold a handle.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct EdgeDetectionRegionMask(pub Handle<Image>);
+
+/// Per-pixel edge sensitivity on a camera with [`EdgeDetection`], for hand-painted
+/// outline control: the image's red channel is sampled at each pixel (stretched
+/// over the viewport) and divides the depth, normal, color and custom thresholds.
+/// 1 leaves them unchanged, lower values make edges harder to trigger (0 removes
+/// them) and values above 1 (float formats) make them easier. Unlike
+/// [`EdgeDetectionRegionMask`] it changes which edges are detected rather than
+/// fading the detected ones. Thresholds are unweighted until the image is loaded.
+///
+/// A separate component because [`EdgeDetection`] is `Copy` and can't hold a handle.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct EdgeDetectionImportance(pub Handle<Image>);
+
 /// Edge detection operator type.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
 pub enum EdgeOperator {
@@ -334,30 +1303,277 @@ pub enum EdgeOperator {
     /// UDLR 4-direction pairwise comparison with silhouette/crease priority.
     /// Guarantees exactly 1px edges. Based on Red Giraffe technique.
     PixelArt,
+    /// Roberts Cross computed from `textureGather`: the 2x2 quad comes back in one
+    /// fetch (depth) or one fetch per channel (normal/color RGB), instead of 4 samples
+    /// per type. The cheapest operator, aimed at mobile/low-power GPUs.
+    ///
+    /// Gather only reads adjacent texels, so thickness is fixed at 1 texel. MSAA views
+    /// can't gather from multisampled textures and fall back to [`Self::RobertsCross`].
+    /// With [`EdgeDetection::depth_prefilter`], depth edges read the filtered depth
+    /// pyramid with 4 samples instead of gathering the unfiltered prepass.
+    Roberts,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub enum ProjectionType {
-    None,
-    Perspective,
-    Orthographic,
+impl EdgeOperator {
+    /// Gradient this operator reports across a view-space depth step of 1: the 3x3
+    /// kernels are normalized to a gain of 4, the 2x2 and pairwise operators take
+    /// plain differences.
+    pub fn depth_step_gain(self) -> f32 {
+        match self {
+            Self::Sobel => 4.0,
+            Self::RobertsCross | Self::PixelArt | Self::Roberts => 1.0,
+        }
+    }
 }
 
-impl From<Option<&Projection>> for ProjectionType {
-    fn from(proj: Option<&Projection>) -> Self {
-        if let Some(projection) = proj {
-            return match projection {
-                Projection::Perspective(_) => Self::Perspective,
-                Projection::Orthographic(_) => Self::Orthographic,
-                Projection::Custom(_) => Self::None,
-            };
-        };
+/// Color space in which color edges measure differences between neighbors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum ColorEdgeSpace {
+    /// Euclidean distance of linear RGB. Reacts to hue-only changes about as much
+    /// as to brightness steps.
+    #[default]
+    Rgb,
+    /// Difference of linear luminance only: shading steps and light/dark borders,
+    /// ignoring hue changes at equal brightness. Differences are at most 1 (vs
+    /// about 1.7 for RGB), so `color_threshold` usually needs lowering.
+    Luminance,
+    /// Euclidean distance in Oklab, which is roughly perceptually uniform: a given
+    /// `color_threshold` triggers on similarly visible changes across hues and
+    /// brightness levels. Lightness runs 0 to 1.
+    Oklab,
+}
 
-        Self::None
-    }
+/// How depth edges compare a pixel with its neighbors.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum DepthEdgeMode {
+    /// The operator's depth gradient (Sobel, Roberts Cross, ...).
+    #[default]
+    Gradient,
+    /// The largest absolute view-depth difference between the pixel and any of its
+    /// 8 neighbors. Catches thin features (wires, fences) that a symmetric gradient
+    /// averages away, at 9 depth samples per pixel. Scaled by the operator's
+    /// [`EdgeOperator::depth_step_gain`], so `depth_threshold` carries over.
+    /// The PixelArt operator ignores it.
+    MaxNeighborDelta,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// Kernel used by the 3x3 operator ([`EdgeOperator::Sobel`]).
+///
+/// Weights are normalized to Sobel's gain, so thresholds carry over between kernels.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum EdgeKernel {
+    /// Classic Sobel: corner weight 1, side weight 2.
+    #[default]
+    Sobel,
+    /// Scharr (3, 10): close to rotation invariant, cleaner diagonal edges.
+    Scharr,
+    /// Prewitt: equal corner and side weights.
+    Prewitt,
+    /// 4-neighbor Laplacian from 5 samples: second derivative instead of a
+    /// gradient, cheaper, with a double-line look on steps. Also fires on creases
+    /// of the depth buffer where the gradient kernels don't.
+    Laplacian,
+}
+
+/// How depth and normal discontinuities are combined.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum DepthNormalMode {
+    /// Depth and normal edges are detected independently and OR'd
+    /// (silhouette takes priority over crease).
+    #[default]
+    Separate,
+    /// Plane-fit detector using depth and normal jointly.
+    ///
+    /// The center pixel's world position `p0` (reconstructed from depth) and
+    /// normal `n0` define its tangent plane. Each of the 4 neighbors at
+    /// `depth_thickness` is reconstructed to `p` and its distance from that
+    /// plane, `|dot(p - p0, n0)|`, is divided by the world-space footprint of
+    /// the sample offset at the center depth. A neighbor further off-plane than
+    /// `plane_threshold` footprints is an edge: a silhouette if its view-z also
+    /// jumps by more than `depth_threshold`, otherwise a crease.
+    ///
+    /// Tilted flat surfaces stay at zero residual regardless of viewing angle,
+    /// so no steep-angle compensation is needed. Only the PixelArt operator
+    /// ignores this mode.
+    JointDepthNormal,
+}
+
+/// Per-frame animation of the resolved edge color, driven by the extracted [`Time`].
+#[derive(Clone, Copy, PartialEq, Debug, Default, Reflect)]
+pub enum EdgeColorAnim {
+    /// Static edge color.
+    #[default]
+    None,
+    /// Oscillates between the edge color and `alt_color`, `speed` cycles per second.
+    Pulse { speed: f32, alt_color: Color },
+    /// Rotates the edge color's hue, `speed` full turns per second.
+    /// Grayscale colors (e.g. black) have no hue and stay unchanged.
+    Hue { speed: f32 },
+}
+
+/// Where the normals used for normal edges (and fresnel, flat rejection, ...) come from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum NormalSource {
+    /// Sample the `NormalPrepass` texture.
+    #[default]
+    Prepass,
+    /// Reconstruct face normals from the depth prepass: world positions of the
+    /// pixel and its neighbors, differenced toward the side with the smaller depth
+    /// step so silhouettes don't smear. No `NormalPrepass` is needed or bound, for
+    /// platforms or materials that don't produce one. Normals are flat (no normal
+    /// maps or smooth shading), the per-entity edge mask reads as
+    /// [`edge_mask::BOTH`], and each normal read costs 5 depth samples.
+    ReconstructFromDepth,
+}
+
+/// Space of the normals [`EdgeColorFromNormal`] is looked up with.
+///
+/// The normal prepass (and [`NormalSource::ReconstructFromDepth`]) produces
+/// world-space normals. Normal edges compare them by distance (`|n_a - n_b|`),
+/// which the world-to-view rotation doesn't change, so normal edges and
+/// `normal_threshold` behave the same in both spaces and as the camera rotates.
+/// Fresnel, rim and steep-angle terms use `N.V` and are space independent too;
+/// `flat_rejection_threshold` always tests the world up axis.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum NormalSpace {
+    /// View-space normals: matcap-style, the lookup follows the screen and the
+    /// image center faces the camera.
+    #[default]
+    View,
+    /// World-space normals: each surface orientation keeps its color while the
+    /// camera orbits.
+    World,
+}
+
+/// How normals are packed in the normal prepass texture, so the shader decodes them
+/// the way they were written.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum NormalEncoding {
+    /// RGB = `n * 0.5 + 0.5`: what Bevy's `NormalPrepass` writes for its materials.
+    #[default]
+    Xyz,
+    /// RG = octahedral-encoded normal in `[0, 1]` (as in Bevy's deferred G-buffer),
+    /// for prepass fragment shaders that pack normals this way. B is ignored; a
+    /// cleared pixel (RG = 0) reads as empty, like a cleared `Xyz` prepass.
+    Octahedral,
+}
+
+/// How the multisampled depth and normal prepasses are read.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum NormalResolve {
+    /// Average every depth and normal sample of a pixel before the kernel runs, so
+    /// edge thickness and placement match `Msaa::Off` whatever the sample count.
+    /// Costs `samples` loads per depth and normal read.
+    #[default]
+    Average,
+    /// Read only the first sample of each pixel: cheapest, but edges shift with the
+    /// sample pattern and on pixels straddling a crease the sample may belong to
+    /// either surface.
+    Sample,
+    /// Average depth as with `Average`, but use the dominant normal sample — the
+    /// normal shared by the most samples — never an average. Sharper creases under
+    /// MSAA, at the cost of `samples^2` comparisons per normal read.
+    Dominant,
+}
+
+/// What happens to outlines along the screen border.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum BorderMode {
+    /// No special handling: neighbor reads past the border follow the texture
+    /// sampler/load behavior, which may produce spurious edges there.
+    #[default]
+    None,
+    /// Clamp neighbor reads to the screen so the border never produces edges.
+    Suppress,
+    /// Like `Suppress`, and deliberately draw a frame in `edge_color` along the
+    /// viewport edge, as wide as the thickest edge source.
+    Draw,
+}
+
+/// How edges are combined with the scene color.
+///
+/// Blending happens in the shader against the sampled scene: the pass writes a
+/// fresh `post_process_write` destination, so a fixed-function `BlendState` would
+/// blend with stale contents. The color target's `blend` therefore stays `None`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum EdgeBlendMode {
+    /// Edge color drawn over the scene by its coverage (edge strength, edge color
+    /// alpha, `edge_opacity`); opaque edges replace the pixel, including its alpha.
+    #[default]
+    Replace,
+    /// Like `Replace`, but the output alpha stays the scene's: edges never add
+    /// coverage, so on transparent render targets they only show over geometry.
+    Alpha,
+    /// Edge color times coverage added to the scene color, for glowing outlines
+    /// (values above 1 survive on HDR cameras, e.g. into bloom with
+    /// `BeforeTonemapping`). The output alpha stays the scene's.
+    Additive,
+}
+
+/// How the resolved edge color varies across the screen.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum EdgeColorMode {
+    /// One color per edge source (`edge_color`, `silhouette_color`, ...).
+    #[default]
+    Solid,
+    /// Blends toward `edge_color_secondary` by the screen-space edge orientation:
+    /// edges whose gradient runs horizontally (vertical lines) keep the resolved
+    /// color, horizontal lines get the secondary color, diagonals a mix. The blend is
+    /// `gradient.y² / |gradient|²`, so both sides of a line match. For a neon look
+    /// with two saturated colors.
+    Direction,
+}
+
+/// Replaces the pass output with a debug visualization.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+pub enum DebugView {
+    /// Regular output.
+    #[default]
+    Off,
+    /// Edge direction encoded as RG = `gradient.xy * 0.5 + 0.5`, B = 0 — the same
+    /// encoding as [`ViewEdgeDirectionTexture`]. Non-edge pixels are (0.5, 0.5, 0).
+    EdgeDirection,
+    /// The distortion noise sampled at each pixel as RG (the UV offset is
+    /// `noise.xy * uv_distortion_strength`), to tune frequency, rotation and phase.
+    DistortionField,
+    /// Edges over black, tinted by the source that produced them: red for depth,
+    /// green for normal, blue for color, white for the custom source and the screen
+    /// border. Tells a false edge from depth noise apart from color aliasing.
+    ChannelColors,
+    /// Final edge strength (after thresholds, masks and fading) as a heatmap from
+    /// dark blue (0) to red (1), to see how close pixels are to the thresholds with
+    /// `edge_smoothness` or `coverage_thickness`.
+    EdgeStrengthHeatmap,
+    /// The decoded world-space prepass normal as RGB = `n * 0.5 + 0.5` (black where
+    /// nothing was drawn), to check that [`NormalEncoding`] matches the prepass.
+    /// Shows the source unchanged on color-only cameras.
+    Normals,
+}
+
+/// Projection path used by the shader for depth linearization and view vectors.
+/// `None` (custom projections) uses the generic inverse-projection path.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectionType {
+    None,
+    Perspective,
+    Orthographic,
+}
+
+impl From<Option<&Projection>> for ProjectionType {
+    fn from(proj: Option<&Projection>) -> Self {
+        if let Some(projection) = proj {
+            return match projection {
+                Projection::Perspective(_) => Self::Perspective,
+                Projection::Orthographic(_) => Self::Orthographic,
+                Projection::Custom(_) => Self::None,
+            };
+        };
+
+        Self::None
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EdgeDetectionKey {
     /// Whether to enable depth-based edge detection.
     /// If `true`, edges will be detected based on depth variations.
@@ -368,8 +1584,73 @@ pub struct EdgeDetectionKey {
     /// Whether to enable color-based edge detection.
     /// If `true`, edges will be detected based on color variations.
     pub enable_color: bool,
+    /// Whether to enable the user-supplied custom edge source.
+    pub enable_custom: bool,
+    /// Whether depth creases are detected (`crease_threshold` is set).
+    pub enable_crease: bool,
     /// Edge detection operator.
     pub operator: EdgeOperator,
+    /// Kernel of the 3x3 operator.
+    pub kernel: EdgeKernel,
+    /// Sample radius of the Sobel gradient kernel, in [1, 3].
+    pub kernel_radius: u32,
+    /// Whether to output edges over a transparent background instead of the scene color.
+    pub transparent_background: bool,
+    /// Whether to gather extra depth texels to recover sub-pixel-thin features.
+    pub thin_feature_recovery: bool,
+    /// How depth edges compare neighbors.
+    pub depth_edge_mode: DepthEdgeMode,
+    /// Whether depth edges sample a [`ViewEdgeDepthPyramid`] level.
+    pub depth_prefilter: bool,
+    /// Whether color and normal reads use the nearest sampler.
+    pub nearest_filtering: bool,
+    /// Color space of color edge differences.
+    pub color_edge_space: ColorEdgeSpace,
+    /// Whether color edges are skipped where the depth prepass is empty.
+    pub skip_transparent: bool,
+    /// How depth and normal discontinuities are combined.
+    pub depth_normal_mode: DepthNormalMode,
+    /// Whether to composite over `flat_background` instead of the scene color.
+    pub flat_background: bool,
+    /// Whether to draw only the outward silhouette halo of the depth prepass.
+    pub silhouette_halo: bool,
+    /// Whether geometry bordering the cleared background is always a depth edge.
+    pub outline_against_background: bool,
+    /// Whether thickness is computed as anti-aliased coverage.
+    pub coverage_thickness: bool,
+    /// Screen-border behavior.
+    pub border_outline: BorderMode,
+    /// Whether edges are colored from an [`EdgeColorFromNormal`] lookup.
+    pub edge_color_from_normal: bool,
+    /// How the edge color varies with the edge direction.
+    pub edge_color_mode: EdgeColorMode,
+    /// How edges are combined with the scene color.
+    pub blend_mode: EdgeBlendMode,
+    /// Whether edges are gated by the motion vector prepass.
+    pub motion_gate: bool,
+    /// Whether edges are masked by an [`EdgeDetectionRegionMask`].
+    pub region_mask: bool,
+    /// Whether thresholds are weighted by an [`EdgeDetectionImportance`] texture.
+    pub importance: bool,
+    /// Whether edges are blended with the reprojected [`ViewEdgeHistoryTextures`].
+    pub temporal: bool,
+    /// How multisampled normals are resolved.
+    pub normal_resolve: NormalResolve,
+    /// Where normals come from.
+    pub normal_source: NormalSource,
+    /// How the normal prepass is decoded.
+    pub normal_encoding: NormalEncoding,
+    /// Space of the normals behind `edge_color_from_normal`.
+    pub normal_space: NormalSpace,
+    /// Whether the pass writes the edge mask, composited by a second pass
+    /// (`resolution_scale` or `edge_dilation`), instead of the composited image.
+    pub mask_composite: bool,
+    /// Whether to write the edge direction to a [`ViewEdgeDirectionTexture`].
+    pub edge_direction_target: bool,
+    /// Whether to write the edge strength to an [`EdgeDetectionMask`].
+    pub edge_mask_target: bool,
+    /// Debug visualization replacing the output.
+    pub debug_view: DebugView,
 
     /// The texture format this view renders to (sourced from `ExtractedView::target_format`).
     pub target_format: TextureFormat,
@@ -377,6 +1658,36 @@ pub struct EdgeDetectionKey {
     pub multisampled: bool,
     /// The projection type of view
     pub projection: ProjectionType,
+    /// No depth/normal detection and no prepass on the view: the depth/normal
+    /// bindings are dropped from the layout and the shader.
+    pub color_only: bool,
+    /// Clamp scene color and output to [0, 1] on an HDR view target
+    /// (see [`EdgeDetectionPlugin::force_ldr`]).
+    pub force_ldr: bool,
+    /// HDR view target without `force_ldr`: the edge color is scaled by
+    /// `edge_emissive_strength`.
+    pub hdr_edges: bool,
+    /// The view is jittered for TAA: prepass reads are shifted back by the jitter.
+    pub unjitter_prepass: bool,
+    /// Whether the scene color is read at all. When `false` the color attachment
+    /// is dropped from the layout and the shader (see [`EdgeDetection::needs_color_source`]).
+    pub color_source: bool,
+}
+
+/// The subset of [`EdgeDetectionKey`] that selects the bind group layout.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct EdgeDetectionLayoutKey {
+    pub multisampled: bool,
+    pub color_only: bool,
+    pub color_source: bool,
+    pub edge_color_from_normal: bool,
+    pub motion_gate: bool,
+    pub region_mask: bool,
+    pub temporal: bool,
+    pub normals_from_depth: bool,
+    pub nearest_filtering: bool,
+    pub importance: bool,
+    pub depth_prefilter: bool,
 }
 
 impl EdgeDetectionKey {
@@ -385,44 +1696,191 @@ impl EdgeDetectionKey {
         target_format: TextureFormat,
         multisampled: bool,
         projection: Option<&Projection>,
+        prepass_available: bool,
     ) -> Self {
+        let color_only = !edge_detection.needs_prepass() && !prepass_available;
         Self {
             enable_depth: edge_detection.enable_depth,
             enable_normal: edge_detection.enable_normal,
             enable_color: edge_detection.enable_color,
+            enable_custom: edge_detection.enable_custom,
+            enable_crease: edge_detection.crease_threshold.is_some() && !color_only,
             operator: edge_detection.operator,
-
-            target_format,
+            kernel: edge_detection.kernel,
+            kernel_radius: edge_detection.kernel_radius.clamp(1, 3),
+            transparent_background: edge_detection.transparent_background,
+            thin_feature_recovery: edge_detection.thin_feature_recovery,
+            depth_edge_mode: edge_detection.depth_edge_mode,
+            depth_prefilter: edge_detection.depth_prefilter > 0
+                && edge_detection.enable_depth
+                && !color_only,
+            nearest_filtering: edge_detection.nearest_filtering,
+            color_edge_space: edge_detection.color_edge_space,
+            skip_transparent: edge_detection.skip_transparent,
+            depth_normal_mode: edge_detection.depth_normal_mode,
+            flat_background: edge_detection.flat_background.is_some(),
+            silhouette_halo: edge_detection.silhouette_halo,
+            outline_against_background: edge_detection.outline_against_background,
+            coverage_thickness: edge_detection.coverage_thickness,
+            border_outline: edge_detection.border_outline,
+            edge_color_from_normal: false,
+            edge_color_mode: edge_detection.edge_color_mode,
+            blend_mode: edge_detection.blend_mode,
+            motion_gate: false,
+            region_mask: false,
+            importance: false,
+            temporal: false,
+            normal_resolve: edge_detection.normal_resolve,
+            normal_source: edge_detection.normal_source,
+            normal_encoding: edge_detection.normal_encoding,
+            normal_space: edge_detection.normal_space,
+            mask_composite: resolution_scale::uses_edge_mask(edge_detection),
+            edge_direction_target: edge_detection.output_edge_direction
+                && !resolution_scale::is_scaled(edge_detection),
+            edge_mask_target: edge_detection.output_edge_mask
+                && !resolution_scale::is_scaled(edge_detection),
+            debug_view: edge_detection.debug_view,
+
+            target_format: if resolution_scale::uses_edge_mask(edge_detection) {
+                EDGE_MASK_TEXTURE_FORMAT
+            } else {
+                target_format
+            },
             multisampled,
             projection: projection.into(),
+            color_only,
+            force_ldr: false,
+            hdr_edges: false,
+            unjitter_prepass: false,
+            // Without a prepass the color attachment is the only texture left.
+            color_source: edge_detection.needs_color_source() || color_only,
+        }
+    }
+
+    /// Variant for 2D views ([`EdgeDetection2dPlugin`]): there is never a prepass,
+    /// so depth/normal settings are dropped and every 2D camera with the same
+    /// color settings shares one pipeline.
+    pub fn color_only_2d(self) -> Self {
+        Self {
+            enable_depth: false,
+            enable_normal: false,
+            enable_color: true,
+            enable_crease: false,
+            thin_feature_recovery: false,
+            depth_edge_mode: DepthEdgeMode::default(),
+            depth_prefilter: false,
+            depth_normal_mode: DepthNormalMode::default(),
+            silhouette_halo: false,
+            outline_against_background: false,
+            skip_transparent: false,
+            normal_resolve: NormalResolve::default(),
+            normal_source: NormalSource::default(),
+            normal_encoding: NormalEncoding::default(),
+            normal_space: NormalSpace::default(),
+            color_only: true,
+            color_source: true,
+            ..self
+        }
+    }
+
+    /// Whether normals are reconstructed from depth instead of bound from the prepass.
+    pub fn normals_from_depth(&self) -> bool {
+        !self.color_only && self.normal_source == NormalSource::ReconstructFromDepth
+    }
+
+    pub fn layout_key(&self) -> EdgeDetectionLayoutKey {
+        EdgeDetectionLayoutKey {
+            multisampled: self.multisampled,
+            color_only: self.color_only,
+            color_source: self.color_source,
+            edge_color_from_normal: self.edge_color_from_normal,
+            motion_gate: self.motion_gate,
+            region_mask: self.region_mask,
+            importance: self.importance,
+            temporal: self.temporal,
+            normals_from_depth: self.normals_from_depth(),
+            nearest_filtering: self.nearest_filtering,
+            depth_prefilter: self.depth_prefilter,
         }
     }
 }
+/// Edge detection settings for a camera.
+///
+/// `DepthPrepass` and `NormalPrepass` are added to the camera when the settings
+/// need them (see [`EdgeDetection::needs_prepass`]). Color-only settings on a
+/// camera without prepasses run a leaner variant that binds no prepass textures;
+/// it is also the only variant that runs where depth prepass textures aren't
+/// supported.
+///
+/// This is a plain component: `On<Add, EdgeDetection>` / `On<Remove, EdgeDetection>`
+/// observers work as usual alongside the plugin's render-world syncing (see the
+/// `observers` example).
 #[derive(Component, Clone, Copy, Debug, Reflect)]
 #[reflect(Component, Default)]
-#[require(DepthPrepass, NormalPrepass)]
+#[cfg_attr(
+    feature = "inspector",
+    derive(InspectorOptions),
+    reflect(InspectorOptions)
+)]
 pub struct EdgeDetection {
+    /// Whether the effect runs on this camera. When `false` nothing is extracted
+    /// to the render world, so no pipeline is specialized and the pass is skipped,
+    /// while the settings stay on the entity for when it is turned back on.
+    pub enabled: bool,
+
     /// Depth threshold, used to detect edges with significant depth changes.
     /// Areas where the depth variation exceeds this threshold will be marked as edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub depth_threshold: f32,
+    /// Depth threshold as a view-space depth step in world units (meters): a jump
+    /// of at least this much between neighboring samples is an edge, whatever the
+    /// operator. Takes precedence over `depth_threshold` when set (see
+    /// [`Self::effective_depth_threshold`]); `None` uses `depth_threshold`.
+    pub depth_threshold_world_units: Option<f32>,
     /// Normal threshold, used to detect edges with significant normal direction changes.
     /// Areas where the normal direction variation exceeds this threshold will be marked as edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub normal_threshold: f32,
+    /// Threshold of depth creases: kinks where a surface folds without a depth jump
+    /// (the inner folds of a toon character), found from the second derivative of
+    /// the prepass depth. Depth edges (`depth_threshold`) keep catching silhouettes,
+    /// creases are drawn in `crease_color` where no depth edge is. Unlike normal
+    /// edges this needs no normal prepass, and ignores normal maps. The curvature is
+    /// relative to the pixel's depth and the screen size, so the threshold holds at
+    /// any distance and resolution; 0.5 to 2 is typical. Not used by
+    /// [`EdgeOperator::PixelArt`]. `None` (the default) disables creases.
+    ///
+    /// Range: [0.0, inf)
+    pub crease_threshold: Option<f32>,
     /// Color threshold, used to detect edges with significant color changes.
     /// Areas where the color variation exceeds this threshold will be marked as edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub color_threshold: f32,
+    /// Color space `color_threshold` is measured in. See [`ColorEdgeSpace`].
+    pub color_edge_space: ColorEdgeSpace,
+    /// Skips color edges on pixels where the depth prepass is empty (at the far
+    /// plane). Transparent and alpha-blended geometry (glass, particles) doesn't
+    /// write the depth and normal prepasses, so it gets no depth/normal outlines but
+    /// still produces color edges; with this set, those against the background are
+    /// dropped instead of drawing ghost outlines. Transparent surfaces in front of
+    /// opaque geometry still produce color edges. Needs the prepass: ignored on
+    /// color-only cameras.
+    pub skip_transparent: bool,
 
     /// Thickness of the edges detected based on depth variations.
     /// This value controls the width of the edges drawn when depth-based edge detection is enabled.
     /// Higher values result in thicker edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub depth_thickness: f32,
     /// Thickness of the edges detected based on normal direction variations.
     /// This value controls the width of the edges drawn when normal-based edge detection is enabled.
     /// Higher values result in thicker edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub normal_thickness: f32,
     /// Thickness of the edges detected based on color variations.
     /// This value controls the width of the edges drawn when color-based edge detection is enabled.
     /// Higher values result in thicker edges.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
     pub color_thickness: f32,
 
     /// Steep angle threshold, used to adjust the depth threshold when viewing surfaces at steep angles.
@@ -431,6 +1889,7 @@ pub struct EdgeDetection {
     /// This threshold defines the angle at which the depth threshold adjustment begins to take effect.
     ///
     /// Range: [0.0, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
     pub steep_angle_threshold: f32,
     /// Multiplier applied to the depth threshold when the view angle is steep.
     /// When the angle between the view direction and the surface normal exceeds the `steep_angle_threshold`,
@@ -440,7 +1899,26 @@ pub struct EdgeDetection {
     /// making edge detection less sensitive in steep angles.
     ///
     /// Range: [0.0, inf)
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
     pub steep_angle_multiplier: f32,
+    /// Subtracted from the depth gradient before it is compared with the depth
+    /// threshold, in the same units as `depth_threshold`: tunes out small-scale depth
+    /// noise (acne-like false edges on large flat surfaces at grazing angles) that
+    /// `steep_angle_multiplier` doesn't remove. 0 (the default) disables it.
+    ///
+    /// Range: [0.0, inf)
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
+    pub depth_bias: f32,
+    /// Number of times the depth used for depth edges is downsampled (2x2 averages
+    /// of linear depth) before the detector samples it: small depth features such as
+    /// foliage average out instead of each getting an outline, and the remaining
+    /// depth edges are softer and `2^depth_prefilter` pixels wide. 0 (the default)
+    /// samples the prepass directly. Costs a full-resolution pass plus one pass per
+    /// level each frame. Normal and color edges are unaffected.
+    ///
+    /// Range: [0, 4]
+    #[cfg_attr(feature = "inspector", inspector(min = 0, max = 4))]
+    pub depth_prefilter: u32,
 
     /// Frequency of UV distortion applied to the edge detection process.
     /// This controls how often the distortion effect repeats across the UV coordinates.
@@ -454,12 +1932,22 @@ pub struct EdgeDetection {
 
     /// Edge color, used to draw the detected edges.
     /// Typically a high-contrast color (e.g., red or black) to visually highlight the edges.
+    ///
+    /// Passed to the shader in linear space and written unchanged; sRGB targets (LDR
+    /// cameras) encode it on write, so with the default pass order the color on
+    /// screen is the same on LDR and HDR cameras. See
+    /// [`EdgeDetectionPassOrder::BeforeTonemapping`] for the exception.
     pub edge_color: Color,
 
     /// Separate color for silhouette (depth) edges. `None` inherits `edge_color`.
     pub silhouette_color: Option<Color>,
-    /// Separate color for crease (normal) edges. `None` inherits `edge_color`.
+    /// Separate color for crease (normal and [`crease_threshold`](Self::crease_threshold))
+    /// edges. `None` inherits `edge_color`.
     pub crease_color: Option<Color>,
+    /// Separate color for color-buffer edges. `None` inherits `edge_color`.
+    /// Together with `silhouette_color` and `crease_color` every edge source can
+    /// have its own color, e.g. for a blueprint look.
+    pub color_edge_color: Option<Color>,
 
     /// Whether to enable depth-based edge detection.
     /// If `true`, edges will be detected based on depth variations.
@@ -471,24 +1959,313 @@ pub struct EdgeDetection {
     /// If `true`, edges will be detected based on color variations.
     pub enable_color: bool,
     /// Pixel block size.
+    #[cfg_attr(feature = "inspector", inspector(min = 1, max = 6))]
     pub block_pixel: u32,
 
     /// Flat surface rejection threshold for the normal Y component.
     /// When all normals in the 3x3 edge detection kernel have Y > this threshold,
     /// the edge is suppressed (treated as a flat surface like hex tiles).
     /// Set to 0.0 to disable flat rejection. Range: [0.0, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
     pub flat_rejection_threshold: f32,
 
     /// Edge detection operator: Sobel (3x3, wider) or RobertsCross (2x2, 1px).
     pub operator: EdgeOperator,
+    /// Kernel of the 3x3 operator: Sobel, Scharr, Prewitt or Laplacian.
+    /// Ignored by the other operators.
+    pub kernel: EdgeKernel,
+    /// Sample radius of the gradient kernel, for smoother detection without
+    /// wider sampling steps: 1 (the default) is the 3x3 kernel, 2 and 3 use 5x5
+    /// and 7x7 kernels with binomial smoothing across the gradient, normalized to
+    /// the 3x3 Sobel gain so thresholds carry over. `*_thickness` still sets the
+    /// distance between samples. Applies to depth, normal and color edges of the
+    /// Sobel operator; `kernel` is ignored above 1 (except `Laplacian`, which
+    /// ignores the radius). Cost grows with `(2 * radius + 1)^2`. Clamped to 3.
+    #[cfg_attr(feature = "inspector", inspector(min = 1, max = 3))]
+    pub kernel_radius: u32,
+
+    /// Output only the edges over a fully transparent background instead of
+    /// compositing them over the scene color. Edge coverage is written to alpha,
+    /// so the result can be alpha-blended over another camera's output
+    /// (see [`EdgeDetectionOverlay`]).
+    pub transparent_background: bool,
+
+    /// Recover depth edges on sub-pixel-thin geometry (wires, grass blades) that
+    /// falls between the operator's depth samples. Adds the max depth spread of the
+    /// two 2x2 texel quads diagonally around each pixel to the depth gradient.
+    ///
+    /// Each quad is fetched with a single `textureGather`, so the cost is two extra
+    /// fetches (8 texels) and 8 depth linearizations per pixel. MSAA targets can't
+    /// gather from multisampled textures and fall back to 8 `textureLoad`s.
+    /// Applies to Sobel and RobertsCross; PixelArt already compares every neighbor.
+    pub thin_feature_recovery: bool,
+    /// How depth edges compare a pixel with its neighbors. See [`DepthEdgeMode`].
+    pub depth_edge_mode: DepthEdgeMode,
+    /// Sample the scene color and normals with nearest instead of bilinear
+    /// filtering. For pixel art: with thickness above 1, distortion or
+    /// `block_pixel`, bilinear reads land between texels and smear the source.
+    pub nearest_filtering: bool,
+
+    /// Animates the resolved edge color over time (pulse between two colors or hue cycling).
+    pub edge_color_animation: EdgeColorAnim,
+
+    /// Quantize the composited output to this many bits per RGB channel
+    /// (e.g. `UVec3::new(5, 6, 5)` or `UVec3::new(3, 3, 2)`) to emulate retro consoles.
+    /// `None` leaves the output untouched.
+    ///
+    /// Quantization is the very last step and happens in sRGB space. The pass runs after
+    /// tonemapping, so on HDR cameras the values are already tonemapped; later passes
+    /// (bloom, anti-aliasing) can reintroduce intermediate values.
+    pub output_bit_depth: Option<UVec3>,
+
+    /// How depth and normal edges are combined: independently (default) or via the
+    /// joint plane-fit detector. See [`DepthNormalMode::JointDepthNormal`].
+    pub depth_normal_mode: DepthNormalMode,
+    /// Plane-fit residual threshold for [`DepthNormalMode::JointDepthNormal`], in
+    /// multiples of the sample offset's world-space footprint.
+    ///
+    /// Range: [0.0, inf)
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, speed = 0.01))]
+    pub plane_threshold: f32,
+
+    /// Scale the thickness values (depth, normal, color and custom) by
+    /// `viewport height / 1080` so outlines keep the same apparent width at any
+    /// resolution (thickness values are tuned for 1080p). Off by default: thickness
+    /// is in texels.
+    ///
+    /// This is the resolution-normalized mode asked for as `thickness_in_pixels`.
+    /// The scale is applied per view in `Prepare` from the extracted viewport
+    /// rather than from `view.viewport` in the shader: it costs no shader variant
+    /// or per-pixel work, and it composes on the CPU with overrides and the
+    /// `resolution_scale` correction before the uniform is written.
+    pub resolution_independent_thickness: bool,
+
+    /// Offset (in noise-texture tiles) applied to the UV distortion noise lookup.
+    /// Give each camera a different phase so split-screen views don't wobble in sync.
+    pub distortion_phase: f32,
+    /// Rotation (radians) applied to the UV before sampling the distortion noise, so
+    /// `uv_distortion_frequency` acts along rotated axes.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = std::f32::consts::TAU))]
+    pub distortion_rotation: f32,
+    /// Scroll rate of the distortion noise, in noise-texture tiles per second.
+    /// Non-zero values animate the wobble instead of freezing it. Zero by default.
+    pub uv_distortion_speed: Vec2,
+
+    /// Whether to detect edges from the user-supplied WGSL edge source
+    /// (see [`EdgeDetectionCustomSource`]). Custom edges use `edge_color` and
+    /// have the lowest priority, after color edges.
+    pub enable_custom: bool,
+    /// Gradient threshold for the custom edge source.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
+    pub custom_threshold: f32,
+    /// Sampling distance (in texels) for the custom edge source.
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 8.0))]
+    pub custom_thickness: f32,
+
+    /// Replace the edge color with black or white, whichever contrasts more with the
+    /// local background luminance. Used by the high-contrast accessibility mode.
+    pub auto_contrast: bool,
+
+    /// Also write the edge direction to a second render target, the view's
+    /// [`ViewEdgeDirectionTexture`], for downstream direction-aware effects.
+    pub output_edge_direction: bool,
+    /// Also write the raw edge strength to an extra render target, the view's
+    /// [`EdgeDetectionMask`], for downstream effects such as glow.
+    pub output_edge_mask: bool,
+    /// Replace the output with a debug visualization. See [`DebugView`].
+    pub debug_view: DebugView,
+
+    /// Line-art mode: composite edges over this flat color instead of the scene.
+    /// With color and custom edges also off, the pass skips binding and sampling
+    /// the scene color entirely.
+    pub flat_background: Option<Color>,
+
+    /// Cheap selection-highlight mode: skip all depth/normal/color/custom detection
+    /// and draw a halo in `silhouette_color` around the outward silhouette of
+    /// whatever is in the depth prepass, fading out over `depth_thickness` texels.
+    /// Meant for masked prepasses; see [`EdgeDetection::selection_highlight`].
+    pub silhouette_halo: bool,
+    /// Always draw a depth edge where geometry borders the cleared background
+    /// (far-plane depth), whatever `depth_threshold` says: the depth step to the
+    /// background is unbounded, so against the sky the gradient test can flicker
+    /// between passing and failing with distance. Needs `enable_depth`.
+    pub outline_against_background: bool,
+
+    /// Quality option: anti-aliased thick outlines. Instead of sampling the operator
+    /// at an offset of `*_thickness` texels (hard, aliased steps), the 1-texel edge
+    /// is dilated over a disc of `thickness` texels and each neighbor is weighted by
+    /// the pixel coverage at its distance, giving a smooth sub-pixel outer boundary.
+    ///
+    /// Cost grows with thickness: about `(thickness + 1)^2` operator evaluations per
+    /// enabled source (9 at thickness 2, 25 at 4) versus one for discrete sampling.
+    /// Use it for thick outlines without MSAA; at thickness 1 it matches the
+    /// discrete result. Applies to Sobel and Roberts Cross without
+    /// `JointDepthNormal`.
+    pub coverage_thickness: bool,
+
+    /// Radial modulation of all edge thresholds. Outside `vignette_radius` the
+    /// thresholds are scaled up to `1 + threshold_vignette` at the screen corners:
+    /// positive values fade outline detail toward the periphery, negative values
+    /// (down to -1) add detail there. 0 leaves thresholds uniform.
+    #[cfg_attr(feature = "inspector", inspector(min = -1.0, speed = 0.01))]
+    pub threshold_vignette: f32,
+    /// Normalized distance from the screen center (1 = corners, in UV space) where
+    /// the threshold vignette starts.
+    ///
+    /// Range: [0.0, 1.0)
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
+    pub vignette_radius: f32,
+
+    /// Screen-border behavior: as is, suppressed, or drawn as a frame. See [`BorderMode`].
+    pub border_outline: BorderMode,
+    /// Limits the edges to this rectangle of the view, in UV space (`(0, 0)` top
+    /// left, `(1, 1)` bottom right); pixels outside it pass the source through
+    /// unchanged, e.g. to compare with and without outlines side by side. `None`
+    /// (the default) covers the whole view. A `flat_background` still fills the
+    /// whole view.
+    pub viewport_rect: Option<Rect>,
+
+    /// Far-field depth threshold. With a non-empty `depth_threshold_ramp`, the
+    /// depth threshold blends from `depth_threshold` (near) to this value (far) by
+    /// linearized view distance, e.g. fine detail up close and only coarse
+    /// silhouettes in the distance. `None` uses `depth_threshold` at all distances.
+    pub depth_threshold_far: Option<f32>,
+    /// View distances (start, end) over which the depth threshold ramps from
+    /// `depth_threshold` to `depth_threshold_far`.
+    pub depth_threshold_ramp: Vec2,
+
+    /// Only outline moving objects: suppress edges on pixels whose screen velocity
+    /// is below `motion_threshold`. Reads the motion vector prepass, so a
+    /// `MotionVectorPrepass` is added to the camera along with the other prepasses;
+    /// without one (or on color-only cameras) the gate is off.
+    pub enable_motion_gate: bool,
+    /// Minimum screen velocity, in pixels per frame, for a pixel to keep its edge.
+    pub motion_threshold: f32,
+
+    /// MSAA depth/normal resolve strategy (see [`NormalResolve`]). Ignored without MSAA.
+    pub normal_resolve: NormalResolve,
+    /// Normal prepass, or normals reconstructed from depth (see [`NormalSource`]).
+    pub normal_source: NormalSource,
+    /// How the normal prepass packs its normals (see [`NormalEncoding`]). Ignored
+    /// with [`NormalSource::ReconstructFromDepth`].
+    pub normal_encoding: NormalEncoding,
+    /// Space of the normals [`EdgeColorFromNormal`] is looked up with (see
+    /// [`NormalSpace`]). Normal edges don't depend on it.
+    pub normal_space: NormalSpace,
+
+    /// Scales the final combined edge strength, after every source, threshold and
+    /// weight: 0 leaves the scene untouched, 1 draws full outlines. The knob to
+    /// animate for fading the whole effect in and out.
+    ///
+    /// Range: [0.0, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
+    pub global_intensity: f32,
+
+    /// Opacity of the drawn edges over the scene color, on top of the edge
+    /// color's own alpha: 0 leaves the scene unchanged, 1 draws opaque edges.
+    ///
+    /// Range: [0.0, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
+    pub edge_opacity: f32,
+    /// Multiplier of the linear edge color, for outlines brighter than 1.0 that
+    /// bleed into a bloom pass running after the edge pass. Only applies on HDR
+    /// cameras (without [`EdgeDetectionPlugin::force_ldr`]); a no-op in SDR, where
+    /// the target can't store values above 1.0 anyway.
+    ///
+    /// Range: [0.0, inf)
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 16.0))]
+    pub edge_emissive_strength: f32,
+    /// How edges are combined with the scene color. See [`EdgeBlendMode`].
+    /// Ignored with `transparent_background`, which outputs the edges alone.
+    pub blend_mode: EdgeBlendMode,
+
+    /// View distance where edges start fading out. See `edge_fade_end`.
+    pub edge_fade_start: f32,
+    /// View distance where edges are fully faded out: edge strength falls linearly
+    /// from 1 at `edge_fade_start` to 0 here, so distant geometry isn't cluttered
+    /// with thin outlines. Distances are linearized for the camera's projection.
+    /// Disabled when not greater than `edge_fade_start` (the default); needs the
+    /// depth prepass, so color-only cameras never fade.
+    pub edge_fade_end: f32,
+
+    /// Resolution of the edge pass relative to the view, for slower GPUs. Below 1
+    /// edges are detected into a smaller texture and upsampled over the scene by a
+    /// second pass: softer edges, much less fragment work (about a quarter at 0.5).
+    /// `output_edge_direction`, `flat_background` and `output_bit_depth` are ignored
+    /// at reduced resolution. 1 (the default) renders at full resolution in one pass.
+    ///
+    /// Range: [0.25, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.25, max = 1.0))]
+    pub resolution_scale: f32,
+    /// Widens the detected edges by this many pixels after detection, as a max over
+    /// a disk in a second pass, instead of widening the detection kernel like the
+    /// `*_thickness` fields: thick lines without the extra false edges a wide kernel
+    /// picks up. Keep thickness at 1 for the cleanest result. 0 (the default)
+    /// disables it; clamped to 8. Uses the same mask pass as `resolution_scale`, so
+    /// `flat_background` and `output_bit_depth` are ignored while it is on.
+    #[cfg_attr(feature = "inspector", inspector(min = 0, max = 8))]
+    pub edge_dilation: u32,
+
+    /// Boosts normal edges at grazing angles, where `1 - |N.V|` is high, so rims of
+    /// smooth curved surfaces (character silhouettes seen head-on) are outlined
+    /// without raising `normal_threshold` everywhere. The normal gradient is scaled
+    /// by `1 + rim_strength * (1 - |N.V|)` before thresholding; 0 disables it.
+    /// Needs normals, so color-only cameras are unaffected.
+    pub rim_strength: f32,
+
+    /// Softens the depth, normal, color and custom thresholds into a smoothstep
+    /// over `threshold * (1 ± edge_smoothness)`, so edge strength ramps in instead
+    /// of switching on: anti-aliased lines without an MSAA/SMAA pass. 0 (the
+    /// default) keeps hard edges. Ignored by the PixelArt operator.
+    ///
+    /// Range: [0.0, 1.0]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 1.0))]
+    pub edge_smoothness: f32,
+
+    /// Temporal stabilization against shimmering under camera motion: how much of
+    /// last frame's edge strength, reprojected with the motion vector prepass, is
+    /// kept at each pixel. 0 (the default) disables it; higher values are steadier
+    /// but trail behind fast motion. A `MotionVectorPrepass` is added to the camera.
+    /// Ignored on color-only cameras, at reduced `resolution_scale` and with `edge_dilation`.
+    ///
+    /// Range: [0.0, 0.95]
+    #[cfg_attr(feature = "inspector", inspector(min = 0.0, max = 0.95))]
+    pub temporal_blend: f32,
+
+    /// Scales depth, normal, color and custom edge thickness by the view distance
+    /// of the surface: 0 (the default) keeps it constant, positive values make far
+    /// outlines thinner (`thickness / (1 + falloff * distance)`), negative values
+    /// make them thicker (up to 4x). Never thins an edge below one pixel. Ignored
+    /// on color-only cameras.
+    #[cfg_attr(feature = "inspector", inspector(min = -0.5, max = 0.5))]
+    pub thickness_depth_falloff: f32,
+
+    /// Per-axis multiplier of `depth_thickness`, `normal_thickness`,
+    /// `color_thickness` and `custom_thickness`, for anisotropic lines: `(1, 3)`
+    /// samples three times farther apart vertically, so horizontal edges get
+    /// thicker while vertical ones don't, e.g. for hatching. `(1, 1)` (the default)
+    /// keeps thickness isotropic. `silhouette_halo` and `coverage_thickness` stay
+    /// isotropic.
+    pub thickness_axis_scale: Vec2,
+
+    /// How the edge color varies with the edge direction. See [`EdgeColorMode`].
+    pub edge_color_mode: EdgeColorMode,
+    /// Second edge color for [`EdgeColorMode::Direction`].
+    pub edge_color_secondary: Color,
 }
 
 impl Default for EdgeDetection {
     fn default() -> Self {
         Self {
+            enabled: true,
+
             depth_threshold: 0.9,
+            depth_threshold_world_units: None,
             normal_threshold: 0.45,
+            crease_threshold: None,
             color_threshold: 0.1,
+            color_edge_space: ColorEdgeSpace::Rgb,
+            skip_transparent: false,
 
             depth_thickness: 1.0,
             normal_thickness: 1.0,
@@ -496,6 +2273,8 @@ impl Default for EdgeDetection {
 
             steep_angle_threshold: 0.75,
             steep_angle_multiplier: 0.30,
+            depth_bias: 0.0,
+            depth_prefilter: 0,
 
             uv_distortion_frequency: Vec2::splat(0.0),
             uv_distortion_strength: Vec2::splat(0.004),
@@ -503,6 +2282,7 @@ impl Default for EdgeDetection {
             edge_color: Color::BLACK,
             silhouette_color: None,
             crease_color: None,
+            color_edge_color: None,
 
             enable_depth: true,
             enable_normal: true,
@@ -513,6 +2293,85 @@ impl Default for EdgeDetection {
             flat_rejection_threshold: 0.0,
 
             operator: EdgeOperator::default(),
+            kernel: EdgeKernel::default(),
+            kernel_radius: 1,
+
+            transparent_background: false,
+            thin_feature_recovery: false,
+            depth_edge_mode: DepthEdgeMode::Gradient,
+            nearest_filtering: false,
+
+            edge_color_animation: EdgeColorAnim::None,
+
+            output_bit_depth: None,
+
+            depth_normal_mode: DepthNormalMode::Separate,
+            plane_threshold: 0.5,
+
+            resolution_independent_thickness: false,
+
+            distortion_phase: 0.0,
+            distortion_rotation: 0.0,
+            uv_distortion_speed: Vec2::ZERO,
+
+            enable_custom: false,
+            custom_threshold: 0.1,
+            custom_thickness: 1.0,
+
+            auto_contrast: false,
+
+            output_edge_direction: false,
+            output_edge_mask: false,
+            debug_view: DebugView::Off,
+
+            flat_background: None,
+
+            silhouette_halo: false,
+            outline_against_background: false,
+
+            coverage_thickness: false,
+
+            threshold_vignette: 0.0,
+            vignette_radius: 0.5,
+
+            border_outline: BorderMode::None,
+            viewport_rect: None,
+
+            depth_threshold_far: None,
+            depth_threshold_ramp: Vec2::new(10.0, 100.0),
+
+            enable_motion_gate: false,
+            motion_threshold: 0.5,
+
+            normal_resolve: NormalResolve::Average,
+            normal_source: NormalSource::Prepass,
+            normal_encoding: NormalEncoding::Xyz,
+            normal_space: NormalSpace::View,
+
+            global_intensity: 1.0,
+
+            edge_opacity: 1.0,
+            edge_emissive_strength: 1.0,
+            blend_mode: EdgeBlendMode::Replace,
+
+            edge_fade_start: 0.0,
+            edge_fade_end: 0.0,
+
+            resolution_scale: 1.0,
+            edge_dilation: 0,
+
+            rim_strength: 0.0,
+
+            edge_smoothness: 0.0,
+
+            temporal_blend: 0.0,
+
+            thickness_depth_falloff: 0.0,
+
+            thickness_axis_scale: Vec2::ONE,
+
+            edge_color_mode: EdgeColorMode::Solid,
+            edge_color_secondary: Color::WHITE,
         }
     }
 }
@@ -539,13 +2398,308 @@ pub struct EdgeDetectionUniform {
 
     pub block_pixel: u32,
     pub flat_rejection_threshold: f32,
+
+    /// Elapsed seconds (wrapped), written during extraction.
+    pub time: f32,
+    /// 0 = none, 1 = pulse, 2 = hue.
+    pub edge_color_anim_mode: u32,
+    pub edge_color_anim_speed: f32,
+    pub edge_color_anim_alt: LinearRgba,
+
+    /// Quantization levels per channel (`2^bits - 1`); 0 disables quantization.
+    pub output_levels: Vec3,
+
+    pub plane_threshold: f32,
+
+    pub distortion_phase: f32,
+
+    pub custom_threshold: f32,
+    pub custom_thickness: f32,
+
+    pub auto_contrast: u32,
+
+    pub flat_background: LinearRgba,
+
+    pub distortion_rotation: f32,
+
+    pub threshold_vignette: f32,
+    pub vignette_radius: f32,
+
+    pub depth_threshold_far: f32,
+    pub depth_ramp: Vec2,
+
+    pub motion_threshold: f32,
+
+    pub global_intensity: f32,
+
+    pub color_edge_color: LinearRgba,
+
+    pub edge_opacity: f32,
+
+    pub edge_fade_start: f32,
+    pub edge_fade_end: f32,
+
+    pub uv_distortion_speed: Vec2,
+
+    pub rim_strength: f32,
+
+    pub edge_smoothness: f32,
+
+    pub temporal_blend: f32,
+
+    pub thickness_depth_falloff: f32,
+
+    pub edge_color_secondary: LinearRgba,
+
+    pub thickness_axis_scale: Vec2,
+
+    pub depth_bias: f32,
+
+    pub viewport_rect: Vec4,
+
+    pub edge_emissive_strength: f32,
+
+    pub crease_threshold: f32,
+
+    pub prepass_jitter: Vec2,
+}
+
+impl EdgeDetection {
+    /// Color-buffer-only preset: color edges plus UV distortion, with no depth or normal
+    /// prepass at all. The lightest way to get a sketch/filter look over any scene.
+    pub fn color_only() -> Self {
+        Self {
+            enable_depth: false,
+            enable_normal: false,
+            enable_color: true,
+            ..default()
+        }
+    }
+
+    /// The depth threshold the shader compares gradients against:
+    /// `depth_threshold_world_units` times the operator's response to a unit depth
+    /// step (see [`EdgeOperator::depth_step_gain`]) when set, else `depth_threshold`.
+    pub fn effective_depth_threshold(&self) -> f32 {
+        match self.depth_threshold_world_units {
+            Some(world_units) => world_units * self.operator.depth_step_gain(),
+            None => self.depth_threshold,
+        }
+    }
+
+    /// Accessibility preset: bold, fully opaque outlines on every geometry boundary, in
+    /// black or white chosen per pixel for maximum contrast with the background.
+    pub fn high_contrast_accessibility() -> Self {
+        Self::default().with_high_contrast()
+    }
+
+    /// These settings with the high-contrast accessibility overrides applied: sensitive
+    /// depth edges, thickness of at least 2 texels, opaque auto-contrast color and no
+    /// distortion. Cameras without a prepass keep color edges only.
+    pub fn with_high_contrast(&self) -> Self {
+        let prepass = self.needs_prepass();
+        Self {
+            depth_threshold: self.effective_depth_threshold().min(0.5),
+            depth_threshold_world_units: None,
+            depth_thickness: self.depth_thickness.max(2.0),
+            normal_thickness: self.normal_thickness.max(2.0),
+            color_thickness: self.color_thickness.max(2.0),
+            enable_depth: prepass,
+            enable_color: self.enable_color || !prepass,
+            edge_color: Color::BLACK,
+            silhouette_color: None,
+            crease_color: None,
+            color_edge_color: None,
+            uv_distortion_strength: Vec2::ZERO,
+            edge_color_animation: EdgeColorAnim::None,
+            auto_contrast: true,
+            ..*self
+        }
+    }
+
+    /// Selection-highlight preset: a `color` halo, `width` texels wide, around the
+    /// silhouette of the depth prepass. Use it on a masked prepass, e.g. via
+    /// [`EdgeDetectionOverlay::selection_highlight`].
+    pub fn selection_highlight(color: Color, width: f32) -> Self {
+        Self {
+            enable_depth: true,
+            enable_normal: false,
+            enable_color: false,
+            depth_thickness: width,
+            edge_color: color,
+            silhouette_color: Some(color),
+            uv_distortion_strength: Vec2::ZERO,
+            silhouette_halo: true,
+            ..default()
+        }
+    }
+
+    /// Whether these settings read the depth/normal prepasses.
+    pub fn needs_prepass(&self) -> bool {
+        self.enable_depth
+            || self.enable_normal
+            || self.depth_normal_mode != DepthNormalMode::Separate
+            || self.silhouette_halo
+            || self.crease_threshold.is_some()
+            || self.debug_view == DebugView::Normals
+    }
+
+    /// Clamps the fields with a documented range into it (thresholds and
+    /// thicknesses `>= 0`, `steep_angle_threshold` in [0, 1], ...; NaN becomes the
+    /// minimum) and returns the names of the fields that changed. The bounds come
+    /// from [`Self::FIELD_RANGES`]; other fields are left as they are.
+    pub fn clamp_to_valid_ranges(&mut self) -> Vec<&'static str> {
+        fn clamp_field(name: &'static str, value: &mut f32, clamped: &mut Vec<&'static str>) {
+            let range = EdgeDetection::field_range(name).expect("clamped field without a range");
+            let new = if value.is_nan() {
+                range.min
+            } else {
+                value.clamp(range.min, range.max)
+            };
+            if new != *value {
+                clamped.push(name);
+            }
+            *value = new;
+        }
+
+        let mut clamped = Vec::new();
+        let fields = [
+            ("depth_threshold", &mut self.depth_threshold),
+            ("normal_threshold", &mut self.normal_threshold),
+            ("color_threshold", &mut self.color_threshold),
+            ("custom_threshold", &mut self.custom_threshold),
+            ("plane_threshold", &mut self.plane_threshold),
+            ("motion_threshold", &mut self.motion_threshold),
+            (
+                "flat_rejection_threshold",
+                &mut self.flat_rejection_threshold,
+            ),
+            ("depth_thickness", &mut self.depth_thickness),
+            ("normal_thickness", &mut self.normal_thickness),
+            ("color_thickness", &mut self.color_thickness),
+            ("custom_thickness", &mut self.custom_thickness),
+            ("steep_angle_threshold", &mut self.steep_angle_threshold),
+            ("steep_angle_multiplier", &mut self.steep_angle_multiplier),
+            ("vignette_radius", &mut self.vignette_radius),
+            ("global_intensity", &mut self.global_intensity),
+            ("edge_opacity", &mut self.edge_opacity),
+            ("resolution_scale", &mut self.resolution_scale),
+            ("rim_strength", &mut self.rim_strength),
+            ("edge_smoothness", &mut self.edge_smoothness),
+            ("temporal_blend", &mut self.temporal_blend),
+        ];
+        for (name, value) in fields {
+            clamp_field(name, value, &mut clamped);
+        }
+        let optional_fields = [
+            ("depth_threshold_far", &mut self.depth_threshold_far),
+            (
+                "depth_threshold_world_units",
+                &mut self.depth_threshold_world_units,
+            ),
+        ];
+        for (name, value) in optional_fields {
+            if let Some(value) = value {
+                clamp_field(name, value, &mut clamped);
+            }
+        }
+        clamped
+    }
+
+    /// Whether these settings read the scene color: for color or custom edges, or
+    /// to composite over it (neither `transparent_background` nor `flat_background`).
+    pub fn needs_color_source(&self) -> bool {
+        self.enable_color
+            || self.enable_custom
+            || !(self.transparent_background || self.flat_background.is_some())
+    }
+}
+
+/// Runtime accessibility toggle. While `true`, every [`EdgeDetection`] camera renders
+/// with [`EdgeDetection::with_high_contrast`] applied; the component itself is left
+/// untouched, so turning it off restores the tuned settings.
+#[derive(Resource, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct HighContrastOutlines(pub bool);
+
+/// Logs an error for [`EdgeDetection`] added to a `Camera2d` when
+/// [`EdgeDetection2dPlugin`] is missing. Without it the pass only runs in the 3D
+/// pipeline (`Core3d`), so on a 2D camera it never runs; this makes the
+/// misconfiguration visible instead of silently doing nothing.
+pub fn validate_edge_detection_cameras(
+    query: Query<(Entity, Option<&Name>), (Added<EdgeDetection>, With<Camera2d>)>,
+) {
+    for (entity, name) in &query {
+        let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+        error!(
+            "EdgeDetection was added to the Camera2d {name}, but 2D cameras need \
+             EdgeDetection2dPlugin (color edges only); without it the effect will not run on \
+             this camera. Add the plugin, or move the component to a Camera3d."
+        );
+    }
+}
+
+/// With [`EdgeDetectionPlugin::validate`]: clamps changed [`EdgeDetection`]
+/// components to their valid ranges (see [`EdgeDetection::clamp_to_valid_ranges`]),
+/// warning once per entity so out-of-range settings don't silently render artifacts.
+pub fn clamp_edge_detection_settings(
+    mut query: Query<(Entity, Option<&Name>, &mut EdgeDetection), Changed<EdgeDetection>>,
+    mut warned: Local<EntityHashSet>,
+) {
+    for (entity, name, mut edge_detection) in &mut query {
+        // Clamp a copy so in-range settings don't trigger change detection again.
+        let mut clamped = *edge_detection;
+        let fields = clamped.clamp_to_valid_ranges();
+        if fields.is_empty() {
+            continue;
+        }
+        *edge_detection = clamped;
+        if warned.insert(entity) {
+            let name = name.map_or_else(|| entity.to_string(), |name| name.to_string());
+            warn!(
+                "EdgeDetection on {name} had out-of-range settings, clamped: {}. Further \
+                 clamping on this entity is silent.",
+                fields.join(", ")
+            );
+        }
+    }
+}
+
+/// Adds `DepthPrepass` + `NormalPrepass` to [`EdgeDetection`] cameras whose
+/// settings read them, both when the component is added and when it changes (e.g.
+/// a color-only camera turning on depth edges). Color-only settings add neither.
+/// Existing prepass components are left untouched, and prepasses are never removed
+/// since other effects may rely on them. 2D cameras are skipped (see
+/// [`validate_edge_detection_cameras`]).
+pub fn insert_edge_detection_prepasses(
+    mut commands: Commands,
+    query: Query<(Entity, &EdgeDetection), (Changed<EdgeDetection>, Without<Camera2d>)>,
+) {
+    for (entity, edge_detection) in &query {
+        if !edge_detection.enabled {
+            continue;
+        }
+        if edge_detection.needs_prepass() {
+            commands.entity(entity).insert_if_new(DepthPrepass);
+            if edge_detection.normal_source == NormalSource::Prepass {
+                commands.entity(entity).insert_if_new(NormalPrepass);
+            }
+        }
+        if edge_detection.enable_motion_gate || edge_detection.temporal_blend > 0.0 {
+            commands.entity(entity).insert_if_new(MotionVectorPrepass);
+        }
+    }
 }
 
 impl From<&EdgeDetection> for EdgeDetectionUniform {
     fn from(ed: &EdgeDetection) -> Self {
         let edge_linear: LinearRgba = ed.edge_color.into();
+        let (anim_mode, anim_speed, anim_alt) = match ed.edge_color_animation {
+            EdgeColorAnim::None => (0, 0.0, edge_linear),
+            EdgeColorAnim::Pulse { speed, alt_color } => (1, speed, alt_color.into()),
+            EdgeColorAnim::Hue { speed } => (2, speed, edge_linear),
+        };
         Self {
-            depth_threshold: ed.depth_threshold,
+            depth_threshold: ed.effective_depth_threshold(),
             normal_threshold: ed.normal_threshold,
             color_threshold: ed.color_threshold,
 
@@ -572,6 +2726,81 @@ impl From<&EdgeDetection> for EdgeDetectionUniform {
 
             block_pixel: ed.block_pixel,
             flat_rejection_threshold: ed.flat_rejection_threshold,
+
+            time: 0.0,
+            edge_color_anim_mode: anim_mode,
+            edge_color_anim_speed: anim_speed,
+            edge_color_anim_alt: anim_alt,
+
+            output_levels: ed.output_bit_depth.map_or(Vec3::ZERO, |bits| {
+                (UVec3::ONE << bits.min(UVec3::splat(16))).as_vec3() - Vec3::ONE
+            }),
+
+            plane_threshold: ed.plane_threshold,
+
+            distortion_phase: ed.distortion_phase,
+
+            custom_threshold: ed.custom_threshold,
+            custom_thickness: ed.custom_thickness,
+
+            auto_contrast: ed.auto_contrast as u32,
+
+            flat_background: ed.flat_background.map_or(LinearRgba::NONE, |c| c.into()),
+
+            distortion_rotation: ed.distortion_rotation,
+
+            threshold_vignette: ed.threshold_vignette,
+            vignette_radius: ed.vignette_radius,
+
+            depth_threshold_far: ed
+                .depth_threshold_far
+                .unwrap_or(ed.effective_depth_threshold()),
+            // An empty ramp disables the blend.
+            depth_ramp: if ed.depth_threshold_far.is_some() {
+                ed.depth_threshold_ramp
+            } else {
+                Vec2::ZERO
+            },
+
+            motion_threshold: ed.motion_threshold,
+
+            global_intensity: ed.global_intensity.clamp(0.0, 1.0),
+
+            color_edge_color: ed.color_edge_color.map(|c| c.into()).unwrap_or(edge_linear),
+
+            edge_opacity: ed.edge_opacity.clamp(0.0, 1.0),
+
+            edge_fade_start: ed.edge_fade_start,
+            edge_fade_end: ed.edge_fade_end,
+
+            uv_distortion_speed: ed.uv_distortion_speed,
+
+            rim_strength: ed.rim_strength.max(0.0),
+
+            edge_smoothness: ed.edge_smoothness.clamp(0.0, 1.0),
+
+            temporal_blend: ed.temporal_blend.clamp(0.0, 0.95),
+
+            thickness_depth_falloff: ed.thickness_depth_falloff,
+
+            edge_color_secondary: ed.edge_color_secondary.into(),
+
+            thickness_axis_scale: ed.thickness_axis_scale.max(Vec2::ZERO),
+
+            depth_bias: ed.depth_bias.max(0.0),
+
+            viewport_rect: ed
+                .viewport_rect
+                .map_or(Vec4::new(0.0, 0.0, 1.0, 1.0), |rect| {
+                    Vec4::new(rect.min.x, rect.min.y, rect.max.x, rect.max.y)
+                }),
+
+            edge_emissive_strength: ed.edge_emissive_strength.max(0.0),
+
+            crease_threshold: ed.crease_threshold.unwrap_or(0.0).max(0.0),
+
+            // Set per view by `unjitter_edge_detection_prepass`.
+            prepass_jitter: Vec2::ZERO,
         }
     }
 }
@@ -579,21 +2808,94 @@ impl From<&EdgeDetection> for EdgeDetectionUniform {
 impl EdgeDetectionUniform {
     pub fn extract_edge_detection_settings(
         mut commands: Commands,
-        mut query: Extract<Query<(RenderEntity, &EdgeDetection)>>,
+        mut query: Extract<
+            Query<(
+                RenderEntity,
+                &EdgeDetection,
+                Option<&EdgeColorFromNormal>,
+                Option<&EdgeDetectionNoise>,
+                Option<&EdgeDetectionRegionMask>,
+                Option<&EdgeDetectionImportance>,
+                Has<Camera2d>,
+            )>,
+        >,
+        time: Extract<Res<Time>>,
+        high_contrast: Extract<Res<HighContrastOutlines>>,
     ) {
-        if !DEPTH_PREPASS_TEXTURE_SUPPORTED {
-            info_once!(
-                "Disable edge detection on this platform because depth textures aren't supported correctly"
-            );
-            return;
-        }
-
-        for (entity, edge_detection) in query.iter_mut() {
+        for (
+            entity,
+            edge_detection,
+            edge_color_from_normal,
+            noise,
+            region_mask,
+            importance,
+            is_2d,
+        ) in query.iter_mut()
+        {
             let mut entity_commands = commands
                 .get_entity(entity)
                 .expect("Edge Detection entity wasn't synced.");
 
-            entity_commands.insert((*edge_detection, EdgeDetectionUniform::from(edge_detection)));
+            if !edge_detection.enabled {
+                // Drop everything derived from the settings, as if the component
+                // were removed, so the view is neither specialized nor rendered.
+                entity_commands.remove::<<EdgeDetection as SyncComponent>::Target>();
+                continue;
+            }
+
+            let edge_detection = if high_contrast.0 {
+                edge_detection.with_high_contrast()
+            } else {
+                *edge_detection
+            };
+
+            // The color-only variant binds no depth texture, so only settings that
+            // read the prepass are dropped on these platforms.
+            if !DEPTH_PREPASS_TEXTURE_SUPPORTED && !is_2d && edge_detection.needs_prepass() {
+                info_once!(
+                    "Disable depth/normal edge detection on this platform because depth textures \
+                     aren't supported correctly; color-only EdgeDetection still runs"
+                );
+                entity_commands.remove::<<EdgeDetection as SyncComponent>::Target>();
+                continue;
+            }
+
+            let mut uniform = EdgeDetectionUniform::from(&edge_detection);
+            uniform.time = time.elapsed_secs_wrapped();
+
+            entity_commands.insert((edge_detection, uniform));
+            match edge_color_from_normal {
+                Some(lookup) => {
+                    entity_commands.insert(lookup.clone());
+                }
+                None => {
+                    entity_commands.remove::<EdgeColorFromNormal>();
+                }
+            }
+            match noise {
+                Some(noise) => {
+                    entity_commands.insert(noise.clone());
+                }
+                None => {
+                    entity_commands.remove::<EdgeDetectionNoise>();
+                }
+            }
+            match region_mask {
+                Some(mask) => {
+                    entity_commands.insert(mask.clone());
+                }
+                None => {
+                    entity_commands.remove::<EdgeDetectionRegionMask>();
+                }
+            }
+            match importance {
+                Some(weights) => {
+                    entity_commands.insert(weights.clone());
+                }
+                None => {
+                    entity_commands.remove::<EdgeDetectionImportance>();
+                }
+            }
         }
     }
 }
@@ -608,55 +2910,202 @@ impl EdgeDetectionUniform {
 /// the view has no [`EdgeDetectionPipelineId`] / [`DynamicUniformIndex`] (i.e. no
 /// [`EdgeDetection`] camera component) the parameter fails validation and the
 /// system is skipped for that view — matching the old `ViewNode` behaviour.
+///
+/// Settings are per view: each camera's [`EdgeDetectionUniform`] lives at its own
+/// dynamic offset in the shared uniform buffer, and the bind group is set with the
+/// current view's [`DynamicUniformIndex`], so split-screen cameras with different
+/// settings don't affect each other (see the `split_screen` example).
 pub fn edge_detection(
     view: ViewQuery<(
-        &Msaa,
         &ViewTarget,
-        &ViewPrepassTextures,
+        Option<&ViewPrepassTextures>,
         &ViewUniformOffset,
         &DynamicUniformIndex<EdgeDetectionUniform>,
         &EdgeDetectionPipelineId,
+        Option<&ViewEdgeDirectionTexture>,
+        Option<&EdgeDetectionMask>,
+        (&EdgeDetection, &MainEntity),
+        &EdgeDetectionUniform,
+        Option<&EdgeColorFromNormal>,
+        Option<&EdgeDetectionNoise>,
+        Option<&EdgeDetectionRegionMask>,
+        Option<&EdgeDetectionImportance>,
+        (
+            Option<&ViewEdgeHistoryTextures>,
+            Option<(
+                &ViewEdgeDepthPyramid,
+                &depth_prefilter::EdgeDepthPrefilterPipelineIds,
+            )>,
+            Option<(&ViewEdgeMergedDepth, &depth_merge::EdgeDepthMergeSources)>,
+        ),
+        Option<(
+            &ViewEdgeMaskTexture,
+            &resolution_scale::EdgeCompositePipelineId,
+        )>,
     )>,
     edge_detection_pipeline: Res<EdgeDetectionPipeline>,
+    composite_pipeline: Res<resolution_scale::EdgeCompositePipeline>,
+    prefilter_pipeline: Res<depth_prefilter::EdgeDepthPrefilterPipeline>,
+    merge_pipeline: Res<depth_merge::EdgeDepthMergePipeline>,
+    merge_sources: Query<&ViewPrepassTextures>,
     pipeline_cache: Res<PipelineCache>,
     gpu_images: Res<RenderAssets<GpuImage>>,
     view_uniforms: Res<ViewUniforms>,
     ed_uniforms: Res<ComponentUniforms<EdgeDetectionUniform>>,
+    frame_count: Res<FrameCount>,
+    pass_log: Res<EdgeDetectionPassLog>,
     mut ctx: RenderContext,
 ) {
     let (
-        msaa,
         view_target,
         prepass_textures,
         view_uniform_index,
         ed_uniform_index,
         edge_detection_pipeline_id,
+        edge_direction_texture,
+        edge_mask,
+        (edge_detection, main_entity),
+        ed_uniform,
+        edge_color_from_normal,
+        noise,
+        region_mask,
+        importance,
+        (history, depth_pyramid, merged_depth),
+        mask_pass,
     ) = view.into_inner();
 
+    // Every early return is recorded, so `EdgeDetectionStats` can tell why nothing
+    // was drawn.
+    let skip = |reason: &'static str| {
+        info!("{reason}");
+        pass_log.record(main_entity.id(), frame_count.0, Some(reason));
+    };
+
     let Some(pipeline) = pipeline_cache.get_render_pipeline(edge_detection_pipeline_id.0) else {
-        info!("pipeline not found");
+        skip("pipeline not found");
         return;
     };
+    let layout_key = edge_detection_pipeline_id.1;
+
+    let prepass_views = if layout_key.color_only {
+        None
+    } else {
+        let Some(depth_texture) = prepass_textures.and_then(|t| t.depth.as_ref()) else {
+            skip("depth texture not found");
+            return;
+        };
+        // Normals reconstructed from depth don't bind the normal prepass.
+        let normal_view = if layout_key.normals_from_depth {
+            None
+        } else {
+            let Some(normal_texture) = prepass_textures.and_then(|t| t.normal.as_ref()) else {
+                skip("normal texture not found");
+                return;
+            };
+            Some(&normal_texture.texture.default_view)
+        };
+        Some((&depth_texture.texture.default_view, normal_view))
+    };
+
+    // Views with an `EdgeDetectionDepthMerge` read the nearest depth of their own
+    // and the merged cameras' prepasses, merged here, in place of their own.
+    let prepass_views = match (merged_depth, prepass_views) {
+        (Some((merged_depth, sources)), Some((depth_view, normal_view))) => {
+            if !depth_merge::merge_depth(
+                &mut ctx,
+                &pipeline_cache,
+                &merge_pipeline,
+                merged_depth,
+                depth_view,
+                merge_sources.iter_many(&sources.0),
+            ) {
+                skip("edge depth merge pipeline not found");
+                return;
+            }
+            Some((&merged_depth.texture.default_view, normal_view))
+        }
+        _ => prepass_views,
+    };
 
-    let (Some(depth_texture), Some(normal_texture)) =
-        (&prepass_textures.depth, &prepass_textures.normal)
+    // Pipelines keyed with `edge_direction_target` get a second attachment; the
+    // texture is present on exactly those views.
+    let direction_attachment = edge_direction_texture.map(|direction| RenderPassColorAttachment {
+        view: &direction.texture.default_view,
+        depth_slice: None,
+        resolve_target: None,
+        ops: Operations {
+            load: LoadOp::Clear(LinearRgba::new(0.5, 0.5, 0.0, 0.0).into()),
+            store: StoreOp::Store,
+        },
+    });
+
+    // A custom noise texture falls back to the embedded one until it is loaded.
+    let Some(noise_texture) = noise
+        .and_then(|noise| gpu_images.get(&noise.0))
+        .or_else(|| gpu_images.get(&edge_detection_pipeline.noise_texture))
     else {
-        info!("depth or normal texture not found");
+        skip("noise texture not found");
         return;
     };
 
-    let Some(noise_texture) = gpu_images.get(&edge_detection_pipeline.noise_texture) else {
-        info!("noise texture not found");
-        return;
+    let motion_vector_view = if layout_key.motion_gate || layout_key.temporal {
+        let Some(motion_vectors) = prepass_textures.and_then(|t| t.motion_vectors.as_ref()) else {
+            skip("motion vector texture not found");
+            return;
+        };
+        Some(&motion_vectors.texture.default_view)
+    } else {
+        None
+    };
+
+    let normal_color_texture = if layout_key.edge_color_from_normal {
+        let Some(lookup) = edge_color_from_normal.and_then(|lookup| gpu_images.get(&lookup.0))
+        else {
+            skip("edge color lookup texture not found");
+            return;
+        };
+        Some(&lookup.texture_view)
+    } else {
+        None
+    };
+
+    let region_mask_texture = if layout_key.region_mask {
+        let Some(mask) = region_mask.and_then(|mask| gpu_images.get(&mask.0)) else {
+            skip("edge region mask texture not found");
+            return;
+        };
+        Some(&mask.texture_view)
+    } else {
+        None
+    };
+
+    let importance_texture = if layout_key.importance {
+        let Some(weights) = importance.and_then(|weights| gpu_images.get(&weights.0)) else {
+            skip("edge importance texture not found");
+            return;
+        };
+        Some(&weights.texture_view)
+    } else {
+        None
+    };
+
+    let history = if layout_key.temporal {
+        let Some(history) = history else {
+            skip("edge history textures not found");
+            return;
+        };
+        Some(history)
+    } else {
+        None
     };
 
     let Some(view_uniforms_binding) = view_uniforms.uniforms.binding() else {
-        info!("view uniforms not found");
+        skip("view uniforms not found");
         return;
     };
 
     let Some(ed_uniform_binding) = ed_uniforms.uniforms().binding() else {
-        info!("edge detection uniform not found");
+        skip("edge detection uniform not found");
         return;
     };
 
@@ -667,6 +3116,43 @@ pub fn edge_detection(
     // [`ViewTarget`] will internally flip the [`ViewTarget`]'s main
     // texture to the `destination` texture. Failing to do so will cause
     // the current main texture information to be lost.
+    // Reduced resolution or dilation: the edge pass writes the mask, then the
+    // composite pass writes `destination`. Both pipelines must be ready before the flip.
+    let mask_pass = match mask_pass {
+        Some((mask, composite_id)) => {
+            let Some(composite) = pipeline_cache.get_render_pipeline(composite_id.0) else {
+                skip("edge composite pipeline not found");
+                return;
+            };
+            Some((mask, composite))
+        }
+        None => None,
+    };
+
+    // Pipelines keyed with `depth_prefilter` sample the depth pyramid, filled here
+    // from this frame's prepass.
+    let depth_pyramid = if layout_key.depth_prefilter {
+        let (Some(depth_pyramid), Some((depth_view, _))) = (depth_pyramid, prepass_views) else {
+            skip("edge depth pyramid not found");
+            return;
+        };
+        if !depth_prefilter::build_depth_pyramid(
+            &mut ctx,
+            &pipeline_cache,
+            &prefilter_pipeline,
+            depth_pyramid,
+            depth_view,
+            view_uniforms_binding.clone(),
+            view_uniform_index.offset,
+        ) {
+            skip("edge depth prefilter pipeline not found");
+            return;
+        }
+        depth_pyramid.0.mips.last()
+    } else {
+        None
+    };
+
     let post_process = view_target.post_process_write();
 
     // The bind_group gets created each frame.
@@ -676,48 +3162,89 @@ pub fn edge_detection(
     // The reason it doesn't work is because each post_process_write will alternate the source/destination.
     // The only way to have the correct source/destination for the bind_group
     // is to make sure you get it during the pass execution.
-    let multisampled = *msaa != Msaa::Off;
-    let bind_group = ctx.render_device().create_bind_group(
-        "edge_detection_bind_group",
-        &pipeline_cache
-            .get_bind_group_layout(edge_detection_pipeline.bind_group_layout(multisampled)),
-        // It's important for this to match the BindGroupLayout defined in the PostProcessPipeline
-        &BindGroupEntries::sequential((
+    let bind_group = edge_detection_pipeline.create_bind_group(
+        ctx.render_device(),
+        &pipeline_cache,
+        layout_key,
+        &EdgeDetectionBindGroupInputs {
             // Make sure to use the source view
-            post_process.source,
-            // Use depth prepass
-            &depth_texture.texture.default_view,
-            // Use normal prepass
-            &normal_texture.texture.default_view,
-            // Use simple texture sampler
-            &edge_detection_pipeline.linear_sampler,
-            // nonfiltering sampler for depth
-            &edge_detection_pipeline.nonfiltering_sampler,
-            // Use noise texture
-            &noise_texture.texture_view,
-            // Use noise texture sampler
-            &edge_detection_pipeline.noise_sampler,
-            // view uniform binding
-            view_uniforms_binding,
-            // Set the uniform binding
-            ed_uniform_binding,
-        )),
+            source: post_process.source,
+            depth: prepass_views.map(|(depth_view, _)| depth_view),
+            normal: prepass_views.and_then(|(_, normal_view)| normal_view),
+            noise: &noise_texture.texture_view,
+            motion_vectors: motion_vector_view,
+            normal_color: normal_color_texture,
+            region_mask: region_mask_texture,
+            history: history.map(|history| &history.read.default_view),
+            importance: importance_texture,
+            depth_pyramid,
+            view_uniforms: view_uniforms_binding,
+            edge_detection_uniforms: ed_uniform_binding,
+        },
     );
 
-    let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
-        label: Some("edge_detection_pass"),
-        color_attachments: &[Some(RenderPassColorAttachment {
-            view: post_process.destination,
+    let mut color_attachments = vec![Some(RenderPassColorAttachment {
+        view: match mask_pass {
+            Some((mask, _)) => &mask.texture.default_view,
+            None => post_process.destination,
+        },
+        depth_slice: None,
+        resolve_target: None,
+        // The fullscreen triangle writes every pixel (no-edge pixels copy the source
+        // exactly), so nothing is cleared and the previous contents never show.
+        ops: Operations {
+            load: LoadOp::Load,
+            store: StoreOp::Store,
+        },
+    })];
+    if direction_attachment.is_some() {
+        color_attachments.push(direction_attachment);
+    }
+    // Pipelines keyed with `edge_mask_target` write the mask at location 2.
+    if let Some(mask) = edge_mask {
+        color_attachments.resize(2, None);
+        color_attachments.push(Some(RenderPassColorAttachment {
+            view: &mask.texture.default_view,
             depth_slice: None,
             resolve_target: None,
             ops: Operations::default(),
-        })],
+        }));
+    }
+    // Pipelines keyed with `temporal` write this frame's history at location 3.
+    if let Some(history) = history {
+        color_attachments.resize(3, None);
+        color_attachments.push(Some(RenderPassColorAttachment {
+            view: &history.write.default_view,
+            depth_slice: None,
+            resolve_target: None,
+            ops: Operations::default(),
+        }));
+    }
+
+    #[cfg(feature = "gpu_timing")]
+    let diagnostics = {
+        use bevy::render::diagnostic::RecordDiagnostics;
+        ctx.diagnostic_recorder()
+    };
+
+    let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("edge_detection_pass"),
+        color_attachments: &color_attachments,
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
         multiview_mask: None,
     });
 
+    // Timestamp queries are written by the span (`EDGE_DETECTION_GPU_TIME`); the
+    // recorder skips them on devices without timestamp support.
+    #[cfg(feature = "gpu_timing")]
+    let pass_span = {
+        use bevy::render::diagnostic::RecordDiagnostics;
+        diagnostics.pass_span(&mut render_pass, "edge_detection")
+    };
+
+    render_pass.push_debug_group(&edge_detection_debug_label(edge_detection, ed_uniform));
     render_pass.set_render_pipeline(pipeline);
     render_pass.set_bind_group(
         0,
@@ -725,4 +3252,117 @@ pub fn edge_detection(
         &[view_uniform_index.offset, ed_uniform_index.index()],
     );
     render_pass.draw(0..3, 0..1);
+    render_pass.pop_debug_group();
+
+    #[cfg(feature = "gpu_timing")]
+    pass_span.end(&mut render_pass);
+    drop(render_pass);
+
+    if let Some((mask, composite)) = mask_pass {
+        resolution_scale::composite_edge_mask(
+            &mut ctx,
+            &pipeline_cache,
+            &composite_pipeline,
+            composite,
+            mask,
+            post_process.source,
+            post_process.destination,
+        );
+    }
+    pass_log.record(main_entity.id(), frame_count.0, None);
+}
+
+/// Debug group label for the pass. With the `debug_annotations` feature it encodes
+/// the enabled sources and live uniform values, so GPU captures (e.g. RenderDoc)
+/// show the parameters inline.
+#[cfg(feature = "debug_annotations")]
+fn edge_detection_debug_label(
+    edge_detection: &EdgeDetection,
+    uniform: &EdgeDetectionUniform,
+) -> std::borrow::Cow<'static, str> {
+    let sources: Vec<&str> = [
+        (edge_detection.enable_depth, "depth"),
+        (edge_detection.enable_normal, "normal"),
+        (edge_detection.enable_color, "color"),
+        (edge_detection.enable_custom, "custom"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+    format!(
+        "edge_detection [{:?}, {:?}, sources={}] threshold d={} n={} c={} thickness d={} n={} c={}",
+        edge_detection.operator,
+        edge_detection.depth_normal_mode,
+        sources.join("+"),
+        uniform.depth_threshold,
+        uniform.normal_threshold,
+        uniform.color_threshold,
+        uniform.depth_thickness,
+        uniform.normal_thickness,
+        uniform.color_thickness,
+    )
+    .into()
+}
+
+#[cfg(not(feature = "debug_annotations"))]
+fn edge_detection_debug_label(
+    _edge_detection: &EdgeDetection,
+    _uniform: &EdgeDetectionUniform,
+) -> std::borrow::Cow<'static, str> {
+    "edge_detection".into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaled_thicknesses(edge_detection: &EdgeDetection, viewport_height: u32) -> [f32; 4] {
+        let mut uniform = EdgeDetectionUniform::from(edge_detection);
+        scale_thickness(edge_detection, viewport_height, &mut uniform);
+        [
+            uniform.depth_thickness,
+            uniform.normal_thickness,
+            uniform.color_thickness,
+            uniform.custom_thickness,
+        ]
+    }
+
+    #[test]
+    fn resolution_independent_thickness_scales_with_viewport_height() {
+        let edge_detection = EdgeDetection {
+            depth_thickness: 1.5,
+            normal_thickness: 2.0,
+            color_thickness: 3.0,
+            custom_thickness: 4.0,
+            resolution_independent_thickness: true,
+            ..default()
+        };
+        let thicknesses = [1.5, 2.0, 3.0, 4.0];
+        assert_eq!(scaled_thicknesses(&edge_detection, 1080), thicknesses);
+        assert_eq!(
+            scaled_thicknesses(&edge_detection, 2160),
+            thicknesses.map(|thickness| thickness * 2.0)
+        );
+    }
+
+    #[test]
+    fn custom_thickness_scales_with_viewport_height() {
+        let edge_detection = EdgeDetection {
+            custom_thickness: 4.0,
+            resolution_independent_thickness: true,
+            ..default()
+        };
+        assert_eq!(scaled_thicknesses(&edge_detection, 1080)[3], 4.0);
+        assert_eq!(scaled_thicknesses(&edge_detection, 2160)[3], 8.0);
+    }
+
+    #[test]
+    fn thickness_is_unscaled_by_default() {
+        let edge_detection = EdgeDetection {
+            depth_thickness: 1.5,
+            ..default()
+        };
+        let unscaled = scaled_thicknesses(&edge_detection, 1080);
+        assert_eq!(scaled_thicknesses(&edge_detection, 2160), unscaled);
+    }
 }
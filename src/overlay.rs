@@ -0,0 +1,319 @@
+//! Edge overlay restricted to a subset of [`RenderLayers`].
+//!
+//! Edge detection is screen-space: it only sees what the depth/normal
+//! prepasses contain, so it cannot tell one entity from another. To outline
+//! only *some* objects (e.g. interactables), the prepass itself has to be
+//! masked. [`EdgeDetectionOverlay`] does this with a second camera:
+//!
+//! 1. A child overlay camera is spawned under the camera carrying
+//!    [`EdgeDetectionOverlay`]. It only renders [`EdgeDetectionOverlay::layers`],
+//!    so its depth/normal prepasses contain nothing but the tagged objects
+//!    (the "masked prepass").
+//! 2. Its [`EdgeDetection`] runs with `transparent_background`, writing edge
+//!    color with edge coverage as alpha and leaving everything else fully
+//!    transparent.
+//! 3. The overlay camera renders after its source camera and composites onto
+//!    the same target with alpha blending via [`CameraOutputMode::Write`]. It
+//!    copies the source's [`RenderTarget`] and [`Hdr`], so overlays on cameras
+//!    rendering to an image or a second window land there too.
+//!
+//! Tagged entities must be visible to *both* cameras, e.g.
+//! `RenderLayers::from_layers(&[0, 1])` with the overlay on layer `1`.
+//! Because the overlay prepass never sees untagged geometry, outlines are not
+//! occluded by it and show through walls. The overlay camera also renders its
+//! own opaque pass for the tagged objects, so keep the layer small.
+//!
+//! # X-ray outlines
+//!
+//! The same mechanism gives x-ray outlines (e.g. allies behind walls): tag
+//! entities with [`XRayOutline`] and give the camera
+//! [`EdgeDetectionOverlay::xray`]. Tagged entities are added to
+//! [`XRAY_OUTLINE_LAYER`], whose prepass never contains the occluders, so their
+//! silhouettes are outlined in the x-ray color whether visible or not.
+//!
+//! # Selection highlights
+//!
+//! [`EdgeDetectionOverlay::selection_highlight`] is the cheap variant for
+//! "highlight the selected object": entities tagged [`SelectionHighlight`] go to
+//! [`SELECTION_HIGHLIGHT_LAYER`] and the overlay only draws a halo around the
+//! silhouette of that masked prepass, skipping normal/color detection entirely.
+//!
+//! Cost: a second camera that runs an opaque pass and depth/normal prepass for
+//! the tagged meshes, one more fullscreen edge pass, and a blended composite.
+//! The outline is drawn over visible parts as well, not only occluded ones.
+
+use bevy::{
+    camera::{CameraOutputMode, CameraUpdateSystems, RenderTarget, visibility::RenderLayers},
+    prelude::*,
+    render::{render_resource::BlendState, view::Hdr},
+};
+
+use crate::EdgeDetection;
+
+/// Outlines only the entities on [`layers`](Self::layers), composited over the
+/// output of the camera this component is attached to. See the
+/// [module docs](self) for how the masked prepass is set up.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct EdgeDetectionOverlay {
+    /// Render layers whose entities receive outlines.
+    pub layers: RenderLayers,
+    /// Edge detection settings used by the overlay camera.
+    /// `transparent_background` is always forced on.
+    pub settings: EdgeDetection,
+}
+
+impl Default for EdgeDetectionOverlay {
+    fn default() -> Self {
+        Self {
+            layers: RenderLayers::layer(1),
+            settings: EdgeDetection::default(),
+        }
+    }
+}
+
+/// Render layer [`XRayOutline`] entities are added to (in addition to their existing layers).
+pub const XRAY_OUTLINE_LAYER: usize = 31;
+
+/// Tags an entity for x-ray outlines; see the [module docs](self).
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct XRayOutline;
+
+/// Render layer [`SelectionHighlight`] entities are added to (in addition to their existing layers).
+pub const SELECTION_HIGHLIGHT_LAYER: usize = 30;
+
+/// Tags an entity for the selection highlight halo; see the [module docs](self).
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct SelectionHighlight;
+
+impl EdgeDetectionOverlay {
+    /// Overlay drawing a `color` halo, `width` texels wide, around
+    /// [`SelectionHighlight`] entities (see [`EdgeDetection::selection_highlight`]).
+    pub fn selection_highlight(color: Color, width: f32) -> Self {
+        Self {
+            layers: RenderLayers::layer(SELECTION_HIGHLIGHT_LAYER),
+            settings: EdgeDetection::selection_highlight(color, width),
+        }
+    }
+
+    /// Overlay outlining [`XRayOutline`] entities through occluders with silhouette-only
+    /// edges in `xray_color`.
+    pub fn xray(xray_color: Color) -> Self {
+        Self {
+            layers: RenderLayers::layer(XRAY_OUTLINE_LAYER),
+            settings: EdgeDetection {
+                enable_normal: false,
+                edge_color: xray_color,
+                silhouette_color: Some(xray_color),
+                ..default()
+            },
+        }
+    }
+}
+
+/// Marker for the overlay camera spawned for an [`EdgeDetectionOverlay`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct EdgeDetectionOverlayCamera {
+    /// The camera carrying the [`EdgeDetectionOverlay`].
+    pub source: Entity,
+}
+
+/// Links a source camera to its spawned overlay camera.
+#[derive(Component)]
+pub(crate) struct OverlayCameraLink(Entity);
+
+impl EdgeDetectionOverlay {
+    fn overlay_settings(&self) -> EdgeDetection {
+        EdgeDetection {
+            transparent_background: true,
+            ..self.settings
+        }
+    }
+}
+
+pub(crate) struct EdgeDetectionOverlayPlugin;
+
+impl Plugin for EdgeDetectionOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EdgeDetectionOverlay>()
+            .register_type::<XRayOutline>()
+            .register_type::<SelectionHighlight>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        add_tag_layer::<XRayOutline, XRAY_OUTLINE_LAYER>,
+                        remove_tag_layer::<XRayOutline, XRAY_OUTLINE_LAYER>,
+                        add_tag_layer::<SelectionHighlight, SELECTION_HIGHLIGHT_LAYER>,
+                        remove_tag_layer::<SelectionHighlight, SELECTION_HIGHLIGHT_LAYER>,
+                    ),
+                    despawn_overlay_cameras,
+                    spawn_overlay_cameras,
+                    sync_overlay_cameras,
+                )
+                    .chain()
+                    .before(CameraUpdateSystems),
+            );
+    }
+}
+
+fn spawn_overlay_cameras(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &EdgeDetectionOverlay,
+            &Camera,
+            Option<&Projection>,
+            Option<&RenderTarget>,
+            Has<Hdr>,
+        ),
+        Without<OverlayCameraLink>,
+    >,
+) {
+    for (entity, overlay, camera, projection, target, hdr) in &query {
+        let mut overlay_camera = commands.spawn((
+            Camera3d::default(),
+            Camera {
+                order: camera.order + 1,
+                clear_color: ClearColorConfig::Custom(Color::NONE),
+                output_mode: CameraOutputMode::Write {
+                    blend_state: Some(BlendState::ALPHA_BLENDING),
+                    clear_color: ClearColorConfig::None,
+                },
+                ..default()
+            },
+            projection.cloned().unwrap_or_default(),
+            target.cloned().unwrap_or_default(),
+            Msaa::Off,
+            overlay.layers.clone(),
+            overlay.overlay_settings(),
+            EdgeDetectionOverlayCamera { source: entity },
+            ChildOf(entity),
+        ));
+        if hdr {
+            overlay_camera.insert(Hdr);
+        }
+        let overlay_camera = overlay_camera.id();
+        commands
+            .entity(entity)
+            .insert(OverlayCameraLink(overlay_camera));
+    }
+}
+
+fn sync_overlay_cameras(
+    mut commands: Commands,
+    sources: Query<
+        (
+            Ref<EdgeDetectionOverlay>,
+            Ref<Camera>,
+            Option<Ref<Projection>>,
+            Option<Ref<RenderTarget>>,
+            Has<Hdr>,
+        ),
+        Without<EdgeDetectionOverlayCamera>,
+    >,
+    mut overlays: Query<
+        (
+            Entity,
+            &EdgeDetectionOverlayCamera,
+            &mut Camera,
+            &mut RenderLayers,
+            &mut EdgeDetection,
+            &mut Projection,
+            &mut RenderTarget,
+            Has<Hdr>,
+        ),
+        Without<EdgeDetectionOverlay>,
+    >,
+) {
+    for (
+        entity,
+        link,
+        mut camera,
+        mut layers,
+        mut edge_detection,
+        mut projection,
+        mut target,
+        hdr,
+    ) in &mut overlays
+    {
+        let Ok((overlay, source_camera, source_projection, source_target, source_hdr)) =
+            sources.get(link.source)
+        else {
+            continue;
+        };
+
+        if overlay.is_changed() {
+            *layers = overlay.layers.clone();
+            *edge_detection = overlay.overlay_settings();
+        }
+
+        if source_camera.is_changed() {
+            camera.order = source_camera.order + 1;
+            camera.is_active = source_camera.is_active;
+            camera.viewport = source_camera.viewport.clone();
+        }
+
+        if let Some(source_projection) = source_projection
+            && source_projection.is_changed()
+        {
+            *projection = source_projection.clone();
+        }
+
+        if let Some(source_target) = source_target
+            && source_target.is_changed()
+        {
+            *target = source_target.clone();
+        }
+
+        if source_hdr && !hdr {
+            commands.entity(entity).insert(Hdr);
+        } else if !source_hdr && hdr {
+            commands.entity(entity).remove::<Hdr>();
+        }
+    }
+}
+
+fn despawn_overlay_cameras(
+    mut commands: Commands,
+    mut removed: RemovedComponents<EdgeDetectionOverlay>,
+    links: Query<&OverlayCameraLink>,
+) {
+    for entity in removed.read() {
+        let Ok(link) = links.get(entity) else {
+            continue;
+        };
+        commands.entity(link.0).despawn();
+        commands.entity(entity).remove::<OverlayCameraLink>();
+    }
+}
+
+/// Adds `LAYER` to entities that just got tag `T`.
+fn add_tag_layer<T: Component, const LAYER: usize>(
+    mut commands: Commands,
+    query: Query<(Entity, Option<&RenderLayers>), Added<T>>,
+) {
+    for (entity, layers) in &query {
+        let layers = layers.cloned().unwrap_or_default().with(LAYER);
+        commands.entity(entity).insert(layers);
+    }
+}
+
+/// Removes `LAYER` from entities that lost tag `T`.
+fn remove_tag_layer<T: Component, const LAYER: usize>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<T>,
+    query: Query<&RenderLayers>,
+) {
+    for entity in removed.read() {
+        let Ok(layers) = query.get(entity) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .insert(layers.clone().without(LAYER));
+    }
+}
@@ -0,0 +1,132 @@
+//! Per-frame overrides of the uniform-backed [`EdgeDetection`] values.
+//!
+//! Settings that only feed the uniform (thresholds, thicknesses, edge color,
+//! distortion strength) can be driven from [`EdgeDetectionOverrides`] instead of
+//! mutating the camera's [`EdgeDetection`] every frame, e.g. for audio-reactive
+//! outlines. The resource is copied into the render world during extraction and
+//! merged into the camera's uniform in `Prepare`, on top of the component values:
+//!
+//! - a `Some` field replaces the component value for that frame,
+//! - `None` keeps the component value,
+//! - removing a camera's entry restores the component settings entirely.
+//!
+//! Overrides are applied before `resolution_independent_thickness` scaling and
+//! after the [`HighContrastOutlines`](crate::HighContrastOutlines) preset, so an
+//! override wins over the accessibility thresholds. Keyed settings (operators,
+//! enabled sources, ...) change the pipeline and stay on the component.
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    render::{Extract, Render, RenderApp, RenderSystems, sync_world::RenderEntity},
+};
+
+use crate::{EdgeDetection, EdgeDetectionUniform, scale_edge_detection_thickness};
+
+/// Uniform values overriding a camera's [`EdgeDetection`] for the current frame.
+/// See the [module docs](self) for how they compose with the component.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+pub struct EdgeDetectionOverride {
+    pub depth_threshold: Option<f32>,
+    pub normal_threshold: Option<f32>,
+    pub color_threshold: Option<f32>,
+    pub depth_thickness: Option<f32>,
+    pub normal_thickness: Option<f32>,
+    pub color_thickness: Option<f32>,
+    /// Replaces `edge_color`, and the silhouette/crease/color-edge colors that inherit it.
+    pub edge_color: Option<Color>,
+    pub uv_distortion_strength: Option<Vec2>,
+}
+
+/// [`EdgeDetectionOverride`]s keyed by camera entity.
+#[derive(Resource, Clone, Debug, Default, Deref, DerefMut)]
+pub struct EdgeDetectionOverrides(pub EntityHashMap<EdgeDetectionOverride>);
+
+pub(crate) struct EdgeDetectionOverridesPlugin;
+
+impl Plugin for EdgeDetectionOverridesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EdgeDetectionOverride>()
+            .init_resource::<EdgeDetectionOverrides>();
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .add_systems(ExtractSchedule, extract_edge_detection_overrides)
+            .add_systems(
+                Render,
+                apply_edge_detection_overrides
+                    .in_set(RenderSystems::Prepare)
+                    .before(scale_edge_detection_thickness)
+                    .before(RenderSystems::PrepareResources),
+            );
+    }
+}
+
+fn extract_edge_detection_overrides(
+    mut commands: Commands,
+    overrides: Extract<Res<EdgeDetectionOverrides>>,
+    query: Extract<Query<(Entity, RenderEntity), With<EdgeDetection>>>,
+) {
+    for (entity, render_entity) in &query {
+        let Ok(mut entity_commands) = commands.get_entity(render_entity) else {
+            continue;
+        };
+        match overrides.get(&entity) {
+            Some(edge_override) => {
+                entity_commands.insert(*edge_override);
+            }
+            None => {
+                entity_commands.remove::<EdgeDetectionOverride>();
+            }
+        }
+    }
+}
+
+/// Merges each view's [`EdgeDetectionOverride`] into its freshly extracted uniform.
+pub fn apply_edge_detection_overrides(
+    mut query: Query<(
+        &EdgeDetection,
+        &EdgeDetectionOverride,
+        &mut EdgeDetectionUniform,
+    )>,
+) {
+    for (edge_detection, edge_override, mut uniform) in &mut query {
+        if let Some(v) = edge_override.depth_threshold {
+            uniform.depth_threshold = v;
+        }
+        if let Some(v) = edge_override.normal_threshold {
+            uniform.normal_threshold = v;
+        }
+        if let Some(v) = edge_override.color_threshold {
+            uniform.color_threshold = v;
+        }
+        if let Some(v) = edge_override.depth_thickness {
+            uniform.depth_thickness = v;
+        }
+        if let Some(v) = edge_override.normal_thickness {
+            uniform.normal_thickness = v;
+        }
+        if let Some(v) = edge_override.color_thickness {
+            uniform.color_thickness = v;
+        }
+        if let Some(color) = edge_override.edge_color {
+            let color: LinearRgba = color.into();
+            uniform.edge_color = color;
+            if edge_detection.silhouette_color.is_none() {
+                uniform.silhouette_color = color;
+            }
+            if edge_detection.crease_color.is_none() {
+                uniform.crease_color = color;
+            }
+            if edge_detection.color_edge_color.is_none() {
+                uniform.color_edge_color = color;
+            }
+        }
+        if let Some(strength) = edge_override.uv_distortion_strength {
+            uniform.uv_distortion.z = strength.x;
+            uniform.uv_distortion.w = strength.y;
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Tuned starting points for common outline styles.
+//!
+//! [`EdgeDetection::from_preset`] returns full settings for an
+//! [`EdgeDetectionPreset`]; override individual fields with struct update syntax
+//! or the `with_*` setters.
+
+use bevy::prelude::*;
+
+use crate::{EdgeDetection, EdgeOperator};
+
+/// Named outline styles for [`EdgeDetection::from_preset`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum EdgeDetectionPreset {
+    /// Bold black outlines on silhouettes and creases, no distortion.
+    Cartoon,
+    /// Thin blue technical lines from depth and normals; color edges and
+    /// distortion off.
+    Blueprint,
+    /// Dark graphite lines from every source, wobbling with high-frequency UV
+    /// distortion like a hand-drawn sketch.
+    Pencil,
+    /// Thick silhouettes only: depth edges, no creases or color edges.
+    Toon,
+}
+
+impl EdgeDetection {
+    /// Settings tuned for `preset`. See [`EdgeDetectionPreset`].
+    pub fn from_preset(preset: EdgeDetectionPreset) -> Self {
+        match preset {
+            EdgeDetectionPreset::Cartoon => Self {
+                operator: EdgeOperator::Sobel,
+                depth_thickness: 1.5,
+                normal_thickness: 1.5,
+                enable_color: false,
+                edge_color: Color::BLACK,
+                uv_distortion_strength: Vec2::ZERO,
+                ..default()
+            },
+            EdgeDetectionPreset::Blueprint => Self {
+                operator: EdgeOperator::RobertsCross,
+                enable_depth: true,
+                enable_normal: true,
+                enable_color: false,
+                normal_threshold: 0.3,
+                edge_color: Color::srgb(0.15, 0.35, 0.85),
+                uv_distortion_frequency: Vec2::ZERO,
+                uv_distortion_strength: Vec2::ZERO,
+                ..default()
+            },
+            EdgeDetectionPreset::Pencil => Self {
+                enable_color: true,
+                color_threshold: 0.2,
+                edge_color: Color::srgb(0.2, 0.2, 0.22),
+                uv_distortion_frequency: Vec2::splat(4.0),
+                uv_distortion_strength: Vec2::splat(0.004),
+                ..default()
+            },
+            EdgeDetectionPreset::Toon => Self {
+                operator: EdgeOperator::Sobel,
+                enable_depth: true,
+                enable_normal: false,
+                enable_color: false,
+                depth_thickness: 2.5,
+                edge_color: Color::BLACK,
+                uv_distortion_strength: Vec2::ZERO,
+                ..default()
+            },
+        }
+    }
+}
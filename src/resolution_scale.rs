@@ -0,0 +1,301 @@
+//! Reduced-resolution edge pass (`EdgeDetection::resolution_scale`).
+//!
+//! Below a scale of 1 the edge shader runs into a [`ViewEdgeMaskTexture`] of
+//! `resolution_scale` times the view size, writing the edge color with its
+//! coverage in alpha (the `TRANSPARENT_BACKGROUND` output). A second fullscreen
+//! pass then upsamples the mask bilinearly and composites it over the
+//! full-resolution scene. Fragment cost drops with the pixel count (about a quarter
+//! at 0.5) for slightly softer edges. Thickness values are divided by the scale, so
+//! lines keep their on-screen width and no boundary falls between two samples.
+//!
+//! Per-pixel output options that need the full-resolution image are not available
+//! in this mode: `output_edge_direction`, `flat_background` and `output_bit_depth`
+//! are ignored.
+//!
+//! `EdgeDetection::edge_dilation` runs the same two passes (at full resolution,
+//! unless scaled too): the composite takes the strongest mask texel within the
+//! dilation radius instead of a single bilinear sample.
+
+use bevy::{
+    asset::{embedded_asset, load_embedded_asset},
+    core_pipeline::FullscreenShader,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        render_resource::{
+            binding_types::{sampler, texture_2d},
+            *,
+        },
+        renderer::{RenderContext, RenderDevice},
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+    },
+};
+
+use crate::{EdgeBlendMode, EdgeDetection, EdgeDetectionSystems};
+
+/// Format of [`ViewEdgeMaskTexture`]: straight edge color, coverage in alpha.
+pub const EDGE_MASK_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Edge output of views with `resolution_scale < 1` or `edge_dilation > 0`.
+#[derive(Component, Clone)]
+pub struct ViewEdgeMaskTexture {
+    pub texture: CachedTexture,
+}
+
+/// Composite pipeline of a view with a [`ViewEdgeMaskTexture`].
+#[derive(Component, Clone, Copy)]
+pub struct EdgeCompositePipelineId(pub CachedRenderPipelineId);
+
+pub(crate) struct EdgeDetectionResolutionScalePlugin;
+
+impl Plugin for EdgeDetectionResolutionScalePlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "edge_composite.wgsl");
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<SpecializedRenderPipelines<EdgeCompositePipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_edge_composite_pipelines.in_set(EdgeDetectionSystems::PreparePipelines),
+                    prepare_edge_mask_textures.in_set(RenderSystems::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<EdgeCompositePipeline>();
+    }
+}
+
+/// Whether these settings run the reduced-resolution path.
+pub(crate) fn is_scaled(edge_detection: &EdgeDetection) -> bool {
+    edge_detection.resolution_scale < 1.0
+}
+
+/// Whether these settings render into a [`ViewEdgeMaskTexture`] and composite it.
+pub(crate) fn uses_edge_mask(edge_detection: &EdgeDetection) -> bool {
+    is_scaled(edge_detection) || edge_detection.edge_dilation > 0
+}
+
+/// The clamped scale actually used for the mask.
+pub(crate) fn effective_scale(edge_detection: &EdgeDetection) -> f32 {
+    edge_detection.resolution_scale.clamp(0.25, 1.0)
+}
+
+#[derive(Resource)]
+pub struct EdgeCompositePipeline {
+    pub layout: BindGroupLayoutDescriptor,
+    pub sampler: Sampler,
+    pub shader: Handle<Shader>,
+    pub fullscreen_shader: FullscreenShader,
+}
+
+impl FromWorld for EdgeCompositePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = load_embedded_asset!(world, "edge_composite.wgsl");
+        let sampler = world
+            .resource::<RenderDevice>()
+            .create_sampler(&SamplerDescriptor {
+                label: Some("edge detection composite sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                ..default()
+            });
+        let stages = ShaderStages::FRAGMENT;
+        let layout = BindGroupLayoutDescriptor::new(
+            "edge_detection: composite bind_group_layout",
+            &[
+                // scene color
+                texture_2d(TextureSampleType::Float { filterable: true }).build(0, stages),
+                // edge mask
+                texture_2d(TextureSampleType::Float { filterable: true }).build(1, stages),
+                sampler(SamplerBindingType::Filtering).build(2, stages),
+            ],
+        );
+        Self {
+            layout,
+            sampler,
+            shader,
+            fullscreen_shader: world.resource::<FullscreenShader>().clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct EdgeCompositeKey {
+    pub target_format: TextureFormat,
+    pub transparent_background: bool,
+    pub force_ldr: bool,
+    /// `edge_dilation` in view pixels, at most 8.
+    pub dilation: u32,
+    pub blend_mode: EdgeBlendMode,
+}
+
+impl SpecializedRenderPipeline for EdgeCompositePipeline {
+    type Key = EdgeCompositeKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = vec![];
+        if key.transparent_background {
+            shader_defs.push("TRANSPARENT_BACKGROUND".into());
+        }
+        if key.force_ldr {
+            shader_defs.push("FORCE_LDR".into());
+        }
+        match key.blend_mode {
+            EdgeBlendMode::Replace => {}
+            EdgeBlendMode::Alpha => shader_defs.push("EDGE_BLEND_ALPHA".into()),
+            EdgeBlendMode::Additive => shader_defs.push("EDGE_BLEND_ADDITIVE".into()),
+        }
+        if key.dilation > 0 {
+            shader_defs.push(ShaderDefVal::UInt("DILATION_RADIUS".into(), key.dilation));
+        }
+
+        RenderPipelineDescriptor {
+            label: Some("edge_detection: composite pipeline".into()),
+            layout: vec![self.layout.clone()],
+            vertex: self.fullscreen_shader.to_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs,
+                entry_point: Some("fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format: key.target_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            immediate_size: 0,
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+fn prepare_edge_composite_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<EdgeCompositePipeline>>,
+    composite_pipeline: Res<EdgeCompositePipeline>,
+    plugin_settings: Res<crate::EdgeDetectionPluginSettings>,
+    query: Query<(
+        Entity,
+        &EdgeDetection,
+        &ViewTarget,
+        Has<EdgeCompositePipelineId>,
+    )>,
+) {
+    for (entity, edge_detection, view_target, has_pipeline) in &query {
+        if !uses_edge_mask(edge_detection) {
+            if has_pipeline {
+                commands.entity(entity).remove::<EdgeCompositePipelineId>();
+            }
+            continue;
+        }
+        let key = EdgeCompositeKey {
+            target_format: view_target.main_texture_format(),
+            transparent_background: edge_detection.transparent_background,
+            force_ldr: plugin_settings.force_ldr && view_target.is_hdr(),
+            dilation: edge_detection.edge_dilation.min(8),
+            blend_mode: edge_detection.blend_mode,
+        };
+        let id = pipelines.specialize(&pipeline_cache, &composite_pipeline, key);
+        commands.entity(entity).insert(EdgeCompositePipelineId(id));
+    }
+}
+
+/// Allocates a [`ViewEdgeMaskTexture`] of `resolution_scale` times the main texture
+/// size for views that use one, and drops it from the others.
+fn prepare_edge_mask_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    query: Query<(
+        Entity,
+        &EdgeDetection,
+        &ViewTarget,
+        Has<ViewEdgeMaskTexture>,
+    )>,
+) {
+    for (entity, edge_detection, view_target, has_texture) in &query {
+        if !uses_edge_mask(edge_detection) {
+            if has_texture {
+                commands.entity(entity).remove::<ViewEdgeMaskTexture>();
+            }
+            continue;
+        }
+
+        let scale = effective_scale(edge_detection);
+        let size = view_target.main_texture().size();
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("edge_detection: edge mask texture"),
+                size: Extent3d {
+                    width: ((size.width as f32 * scale).round() as u32).max(1),
+                    height: ((size.height as f32 * scale).round() as u32).max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: EDGE_MASK_TEXTURE_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewEdgeMaskTexture { texture });
+    }
+}
+
+/// Upsamples `mask` over `source` into `destination` with the composite `pipeline`.
+pub(crate) fn composite_edge_mask(
+    ctx: &mut RenderContext,
+    pipeline_cache: &PipelineCache,
+    composite_pipeline: &EdgeCompositePipeline,
+    pipeline: &RenderPipeline,
+    mask: &ViewEdgeMaskTexture,
+    source: &TextureView,
+    destination: &TextureView,
+) {
+    let bind_group = ctx.render_device().create_bind_group(
+        "edge_detection_composite_bind_group",
+        &pipeline_cache.get_bind_group_layout(&composite_pipeline.layout),
+        &BindGroupEntries::sequential((
+            source,
+            &mask.texture.default_view,
+            &composite_pipeline.sampler,
+        )),
+    );
+
+    let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("edge_detection_composite_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: destination,
+            depth_slice: None,
+            resolve_target: None,
+            // Every pixel is written, as in the edge pass.
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    });
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, &bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
@@ -0,0 +1,170 @@
+//! Per-camera report of whether the edge pass ran ([`EdgeDetectionStats`]), and
+//! whether its pipelines are compiled ([`EdgeDetectionReady`]).
+//!
+//! The pass returns early, drawing nothing, while something it binds isn't ready
+//! (pipeline still compiling, a texture not loaded yet, ...). Each time it runs for
+//! a view it records which path it took, and the report is copied back to the
+//! camera in the main world at the start of the next frame.
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    render::{Render, RenderApp, RenderSystems, render_resource::PipelineCache},
+};
+
+use crate::{
+    EdgeDetectionPipelineId, EdgeDetectionSystems,
+    depth_merge::{EdgeDepthMergePipeline, EdgeDepthMergeSources},
+    depth_prefilter::EdgeDepthPrefilterPipelineIds,
+    resolution_scale::EdgeCompositePipelineId,
+};
+
+/// Outcome of the edge pass on this camera, inserted on [`EdgeDetection`] cameras
+/// once the pass first runs for them.
+///
+/// With pipelined rendering the report lags the main world by a frame. It is only
+/// updated when the pass system runs for the view: if `frame` stops advancing, the
+/// pass isn't scheduled for the camera at all (e.g. `enabled` is false, or the
+/// view isn't rendered).
+///
+/// [`EdgeDetection`]: crate::EdgeDetection
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct EdgeDetectionStats {
+    /// Whether the edges were drawn.
+    pub ran: bool,
+    /// [`FrameCount`](bevy::diagnostic::FrameCount) of the rendered frame.
+    pub frame: u32,
+    /// Why the pass returned early, e.g. `"pipeline not found"` while the shader is
+    /// compiling. `None` when it ran.
+    pub skipped: Option<&'static str>,
+}
+
+/// Reports shared between the render world (writer) and the main world (reader).
+#[derive(Resource, Clone, Default)]
+pub struct EdgeDetectionPassLog(Arc<Mutex<EntityHashMap<EdgeDetectionStats>>>);
+
+impl EdgeDetectionPassLog {
+    /// Records the outcome of the pass for the camera `main_entity`.
+    pub fn record(&self, main_entity: Entity, frame: u32, skipped: Option<&'static str>) {
+        let stats = EdgeDetectionStats {
+            ran: skipped.is_none(),
+            frame,
+            skipped,
+        };
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(main_entity, stats);
+    }
+}
+
+/// Whether every edge-detection view has its pipelines compiled, so the effect
+/// draws from now on instead of popping in a few frames later, e.g. to hold a
+/// loading screen until it turns `true`.
+///
+/// Updated in the render world during [`RenderSystems::Prepare`], after
+/// [`EdgeDetectionSystems::PreparePipelines`], and mirrored to the main world at
+/// the start of the next frame. `false` while there is no edge-detection view yet.
+/// A setting change that needs a new pipeline variant turns it `false` again until
+/// the variant is compiled.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct EdgeDetectionReady(pub bool);
+
+/// Flag shared between the render world (writer) and the main world (reader).
+#[derive(Resource, Clone, Default)]
+struct SharedEdgeDetectionReady(Arc<AtomicBool>);
+
+pub(crate) struct EdgeDetectionStatsPlugin;
+
+impl Plugin for EdgeDetectionStatsPlugin {
+    fn build(&self, app: &mut App) {
+        let log = EdgeDetectionPassLog::default();
+        let ready = SharedEdgeDetectionReady::default();
+        app.register_type::<EdgeDetectionStats>()
+            .insert_resource(log.clone())
+            .insert_resource(ready.clone())
+            .init_resource::<EdgeDetectionReady>()
+            .add_systems(
+                First,
+                (update_edge_detection_stats, mirror_edge_detection_ready),
+            );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(log)
+            .insert_resource(ready)
+            .init_resource::<EdgeDetectionReady>()
+            .add_systems(
+                Render,
+                check_edge_detection_ready
+                    .in_set(RenderSystems::Prepare)
+                    .after(EdgeDetectionSystems::PreparePipelines),
+            );
+    }
+}
+
+fn check_edge_detection_ready(
+    pipeline_cache: Res<PipelineCache>,
+    mut ready: ResMut<EdgeDetectionReady>,
+    shared: Res<SharedEdgeDetectionReady>,
+    merge_pipeline: Res<EdgeDepthMergePipeline>,
+    views: Query<(
+        &EdgeDetectionPipelineId,
+        Option<&EdgeCompositePipelineId>,
+        Option<&EdgeDepthPrefilterPipelineIds>,
+        Has<EdgeDepthMergeSources>,
+    )>,
+) {
+    let compiled = |id| pipeline_cache.get_render_pipeline(id).is_some();
+    let all_ready = !views.is_empty()
+        && views
+            .iter()
+            .all(|(pipeline_id, composite, prefilter, merged)| {
+                pipeline_id.is_ready(&pipeline_cache)
+                    && composite.is_none_or(|composite| compiled(composite.0))
+                    && prefilter.is_none_or(|prefilter| {
+                        compiled(prefilter.linearize) && compiled(prefilter.downsample)
+                    })
+                    && (!merged || compiled(merge_pipeline.pipeline_id))
+            });
+    ready.set_if_neq(EdgeDetectionReady(all_ready));
+    shared.0.store(all_ready, Ordering::Relaxed);
+}
+
+fn mirror_edge_detection_ready(
+    shared: Res<SharedEdgeDetectionReady>,
+    mut ready: ResMut<EdgeDetectionReady>,
+) {
+    ready.set_if_neq(EdgeDetectionReady(shared.0.load(Ordering::Relaxed)));
+}
+
+fn update_edge_detection_stats(
+    mut commands: Commands,
+    log: Res<EdgeDetectionPassLog>,
+    mut cameras: Query<&mut EdgeDetectionStats>,
+) {
+    let reports = std::mem::take(
+        &mut *log
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    for (entity, stats) in reports {
+        match cameras.get_mut(entity) {
+            Ok(mut current) => {
+                current.set_if_neq(stats);
+            }
+            Err(_) => {
+                commands.entity(entity).try_insert(stats);
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Temporal edge stabilization (`EdgeDetection::temporal_blend`).
+//!
+//! Thin outlines shimmer under camera motion as they snap between pixels. With a
+//! `temporal_blend` above 0 the edge pass also writes its final edge strength to a
+//! per-view history texture, and on the next frame reprojects that history with
+//! the motion vector prepass and blends it into the new edge strength. The two
+//! history textures ping-pong by frame parity, like Bevy's TAA history.
+//!
+//! Needs the motion vector prepass (added to the camera automatically); color-only
+//! cameras and the edge mask path (`resolution_scale < 1`, `edge_dilation > 0`)
+//! don't stabilize. History is discarded on the first frame of a view.
+
+use bevy::{
+    diagnostic::FrameCount,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        render_resource::*,
+        renderer::RenderDevice,
+        texture::{CachedTexture, TextureCache},
+        view::ViewTarget,
+    },
+};
+
+use crate::{EdgeDetection, EdgeDetectionUniform, resolution_scale};
+
+/// Format of [`ViewEdgeHistoryTextures`]: edge strength in R.
+pub const EDGE_HISTORY_TEXTURE_FORMAT: TextureFormat = TextureFormat::R16Float;
+
+/// Edge strength history of views with `temporal_blend > 0`: last frame's output is
+/// `read`, this frame's is written to `write`.
+#[derive(Component, Clone)]
+pub struct ViewEdgeHistoryTextures {
+    pub write: CachedTexture,
+    pub read: CachedTexture,
+}
+
+pub(crate) struct EdgeDetectionTemporalPlugin;
+
+impl Plugin for EdgeDetectionTemporalPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.add_systems(
+            Render,
+            (
+                discard_new_edge_history
+                    .in_set(RenderSystems::Prepare)
+                    .before(RenderSystems::PrepareResources),
+                prepare_edge_history_textures.in_set(RenderSystems::PrepareResources),
+            ),
+        );
+    }
+}
+
+/// Whether these settings keep an edge history. Views that can't stabilize (no
+/// motion vectors, color-only) still get the textures; the key decides the rest.
+pub(crate) fn is_temporal(edge_detection: &EdgeDetection) -> bool {
+    edge_detection.temporal_blend > 0.0 && !resolution_scale::uses_edge_mask(edge_detection)
+}
+
+/// Zeroes the blend on the frame a view's history is allocated: the cached texture
+/// may hold another view's (or stale) contents.
+fn discard_new_edge_history(
+    mut query: Query<(&EdgeDetection, &mut EdgeDetectionUniform), Without<ViewEdgeHistoryTextures>>,
+) {
+    for (edge_detection, mut uniform) in &mut query {
+        if is_temporal(edge_detection) {
+            uniform.temporal_blend = 0.0;
+        }
+    }
+}
+
+/// Allocates the [`ViewEdgeHistoryTextures`] pair matching the main texture size for
+/// views with `temporal_blend > 0`, and drops it from views without.
+fn prepare_edge_history_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    frame_count: Res<FrameCount>,
+    query: Query<(
+        Entity,
+        &EdgeDetection,
+        &ViewTarget,
+        Has<ViewEdgeHistoryTextures>,
+    )>,
+) {
+    for (entity, edge_detection, view_target, has_textures) in &query {
+        if !is_temporal(edge_detection) {
+            if has_textures {
+                commands.entity(entity).remove::<ViewEdgeHistoryTextures>();
+            }
+            continue;
+        }
+
+        let mut descriptor = TextureDescriptor {
+            label: Some("edge_detection: edge history 1 texture"),
+            size: view_target.main_texture().size(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: EDGE_HISTORY_TEXTURE_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        let history_1 = texture_cache.get(&render_device, descriptor.clone());
+        descriptor.label = Some("edge_detection: edge history 2 texture");
+        let history_2 = texture_cache.get(&render_device, descriptor);
+
+        let textures = if frame_count.0.is_multiple_of(2) {
+            ViewEdgeHistoryTextures {
+                write: history_1,
+                read: history_2,
+            }
+        } else {
+            ViewEdgeHistoryTextures {
+                write: history_2,
+                read: history_1,
+            }
+        };
+        commands.entity(entity).insert(textures);
+    }
+}
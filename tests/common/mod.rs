@@ -0,0 +1,333 @@
+//! Headless render harness shared by the GPU checks in `tests/`.
+//!
+//! A check builds an [`App`] with [`headless_app`], spawns a scene rendering into
+//! an image from [`render_target`] and reads the image back with [`read_back`]
+//! once the edge pipelines are compiled. No window is opened and frames are
+//! stepped by hand without pipelined rendering, so a check can also inspect the
+//! render world between frames.
+//!
+//! The check binaries are `harness = false` test targets: [`run`] runs their named
+//! checks, each in a fresh app, and fails the process if any failed. Without a
+//! GPU adapter (not even a software one) the checks are skipped, so
+//! `cargo test` still passes on such machines.
+
+// Each check binary uses a different subset of the harness.
+#![allow(dead_code)]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    app::{Plugins, PluginsState},
+    asset::RenderAssetUsages,
+    camera::RenderTarget,
+    core_pipeline::tonemapping::{DebandDither, Tonemapping},
+    log::LogPlugin,
+    prelude::*,
+    render::{
+        gpu_readback::{Readback, ReadbackComplete},
+        pipelined_rendering::PipelinedRenderingPlugin,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    },
+    tasks::tick_global_task_pools_on_main_thread,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use bevy_edge_detection_outline::{EdgeDetectionPlugin, EdgeDetectionReady};
+
+/// Frames rendered before a readback is accepted, on top of waiting for
+/// [`EdgeDetectionReady`].
+pub const WARMUP_FRAMES: u32 = 5;
+/// Frames after which [`read_back`] gives up.
+pub const TIMEOUT_FRAMES: u32 = 600;
+/// Frames between [`EdgeDetectionReady`] turning `true` and the first accepted
+/// readback: the flag lags the render world by a frame and a readback arrives a
+/// frame or two after the frame it copied.
+const READBACK_LATENCY: u32 = 3;
+
+/// A named check: `Ok` when it passed (or was skipped), `Err` with the reason.
+pub type Check = (&'static str, fn() -> Result<(), String>);
+
+/// Runs `checks` in order and reports each one; fails if any failed.
+pub fn run(checks: &[Check]) -> ExitCode {
+    let mut failed = 0;
+    for (name, check) in checks {
+        match check() {
+            Ok(()) => println!("check {name} ... ok"),
+            Err(message) => {
+                println!("check {name} ... FAILED: {message}");
+                failed += 1;
+            }
+        }
+    }
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        println!("{failed} of {} checks failed", checks.len());
+        ExitCode::FAILURE
+    }
+}
+
+/// A windowless app with [`EdgeDetectionPlugin`] and the extra `plugins`, its
+/// plugins finished and ready to step with [`App::update`]. `None` (after
+/// printing why) when there is no GPU adapter to render with.
+pub fn headless_app<M>(plugins: impl Plugins<M>) -> Option<App> {
+    headless_app_with(EdgeDetectionPlugin::default(), plugins)
+}
+
+/// [`headless_app`] with the given [`EdgeDetectionPlugin`] options.
+pub fn headless_app_with<M>(
+    edge_detection: EdgeDetectionPlugin,
+    plugins: impl Plugins<M>,
+) -> Option<App> {
+    let built = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut app = App::new();
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<PipelinedRenderingPlugin>()
+                .disable::<LogPlugin>(),
+            edge_detection,
+        ))
+        .add_plugins(plugins);
+        // The renderer is created asynchronously; wait for it like the default runner.
+        while app.plugins_state() == PluginsState::Adding {
+            tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        app
+    }));
+    match built {
+        Ok(app) => Some(app),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or_default();
+            // `RenderPlugin` panics with this when no adapter is available.
+            if !message.contains("Unable to find a GPU") {
+                panic::resume_unwind(payload);
+            }
+            println!("skipped: no GPU adapter");
+            None
+        }
+    }
+}
+
+fn bytes_per_pixel(format: TextureFormat) -> usize {
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => 4,
+        TextureFormat::Rgba16Float => 8,
+        _ => panic!("unsupported readback format {format:?}"),
+    }
+}
+
+/// A cleared image of `size` that cameras can render into and [`read_back`] can
+/// copy from. Rows must be a multiple of 256 bytes, so the readback has no row
+/// padding (64 pixels for 8-bit, 32 for 16-bit float formats).
+pub fn render_target(app: &mut App, size: UVec2, format: TextureFormat) -> Handle<Image> {
+    let pixel_size = bytes_per_pixel(format);
+    assert_eq!(
+        size.x as usize * pixel_size % 256,
+        0,
+        "render target rows must be 256-byte aligned"
+    );
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &vec![0; pixel_size],
+        format,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC;
+    app.world_mut().resource_mut::<Assets<Image>>().add(image)
+}
+
+/// Unlit blue unit cube resting on an unlit gray plane, with a white clear color:
+/// apart from the outlines the image only holds these three flat colors.
+/// Cameras looking at `(0, 0.5, 0)` see the cube in the middle of the image.
+/// Returns the cube and plane entities.
+pub fn spawn_cube_on_plane(app: &mut App) -> (Entity, Entity) {
+    let world = app.world_mut();
+    world.insert_resource(ClearColor(Color::WHITE));
+    let cube = world.resource_mut::<Assets<Mesh>>().add(Cuboid::default());
+    let plane = world
+        .resource_mut::<Assets<Mesh>>()
+        .add(Plane3d::default().mesh().size(20.0, 20.0));
+    let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+    let cube_material = materials.add(StandardMaterial {
+        base_color: CUBE_COLOR,
+        unlit: true,
+        ..default()
+    });
+    let plane_material = materials.add(StandardMaterial {
+        base_color: PLANE_COLOR,
+        unlit: true,
+        ..default()
+    });
+    let cube = world
+        .spawn((
+            Mesh3d(cube),
+            MeshMaterial3d(cube_material),
+            Transform::from_xyz(0.0, 0.5, 0.0),
+        ))
+        .id();
+    let plane = world
+        .spawn((Mesh3d(plane), MeshMaterial3d(plane_material)))
+        .id();
+    (cube, plane)
+}
+
+/// Camera over [`spawn_cube_on_plane`] rendering into `target`, without MSAA,
+/// tonemapping or dithering, so pixels are the exact pass output.
+pub fn cube_camera(target: Handle<Image>) -> impl Bundle {
+    (
+        Camera3d::default(),
+        RenderTarget::Image(target.into()),
+        Transform::from_xyz(0.0, 1.5, 4.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+        Msaa::Off,
+        Tonemapping::None,
+        DebandDither::Disabled,
+    )
+}
+
+/// Base color of the cube in [`spawn_cube_on_plane`].
+pub const CUBE_COLOR: Color = Color::srgb(0.1, 0.2, 0.9);
+/// Base color of the plane in [`spawn_cube_on_plane`].
+pub const PLANE_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Pixels copied back from a render target.
+pub struct Readout {
+    pub data: Vec<u8>,
+    pub size: UVec2,
+    pub format: TextureFormat,
+}
+
+impl Readout {
+    fn offset(&self, x: u32, y: u32) -> usize {
+        (y * self.size.x + x) as usize * bytes_per_pixel(self.format)
+    }
+
+    /// Raw channels of an 8-bit pixel (still sRGB-encoded for sRGB formats).
+    pub fn rgba8(&self, x: u32, y: u32) -> [u8; 4] {
+        assert_eq!(bytes_per_pixel(self.format), 4, "not an 8-bit format");
+        let i = self.offset(x, y);
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+
+    /// The pixel as a linear color: sRGB formats are decoded, unorm values
+    /// divided by 255, half floats widened.
+    pub fn linear(&self, x: u32, y: u32) -> LinearRgba {
+        let i = self.offset(x, y);
+        match self.format {
+            TextureFormat::Rgba16Float => {
+                let channel = |c: usize| {
+                    half_to_f32(u16::from_le_bytes([
+                        self.data[i + 2 * c],
+                        self.data[i + 2 * c + 1],
+                    ]))
+                };
+                LinearRgba::new(channel(0), channel(1), channel(2), channel(3))
+            }
+            TextureFormat::Rgba8UnormSrgb => {
+                let [r, g, b, a] = self.rgba8(x, y);
+                Srgba::rgba_u8(r, g, b, a).into()
+            }
+            _ => {
+                let [r, g, b, a] = self.rgba8(x, y);
+                LinearRgba::new(
+                    r as f32 / 255.0,
+                    g as f32 / 255.0,
+                    b as f32 / 255.0,
+                    a as f32 / 255.0,
+                )
+            }
+        }
+    }
+
+    /// Raw bytes of a pixel, for exact comparisons.
+    pub fn bytes(&self, x: u32, y: u32) -> &[u8] {
+        let i = self.offset(x, y);
+        &self.data[i..i + bytes_per_pixel(self.format)]
+    }
+}
+
+/// IEEE 754 binary16 to `f32`.
+fn half_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+/// Steps `app` until the edge pipelines are compiled and returns the next
+/// contents of `target`.
+pub fn read_back(app: &mut App, target: &Handle<Image>) -> Result<Readout, String> {
+    let (size, format) = {
+        let image = app
+            .world()
+            .resource::<Assets<Image>>()
+            .get(target)
+            .ok_or("render target image missing")?;
+        (image.size(), image.texture_descriptor.format)
+    };
+    let received = Arc::new(Mutex::new(None));
+    let slot = received.clone();
+    let readback = app
+        .world_mut()
+        .spawn(Readback::texture(target.clone()))
+        .observe(move |readback: On<ReadbackComplete>| {
+            *slot.lock().unwrap() = Some(readback.data.clone());
+        })
+        .id();
+
+    let mut ready_frames = 0;
+    let mut result = Err(format!("no readback after {TIMEOUT_FRAMES} frames"));
+    for frame in 0..TIMEOUT_FRAMES {
+        app.update();
+        if frame >= WARMUP_FRAMES && app.world().resource::<EdgeDetectionReady>().0 {
+            ready_frames += 1;
+        }
+        let data = received.lock().unwrap().take();
+        if ready_frames > READBACK_LATENCY
+            && let Some(data) = data
+        {
+            let expected = (size.x * size.y) as usize * bytes_per_pixel(format);
+            result = if data.len() < expected {
+                Err(format!(
+                    "readback is {} bytes, expected {expected}",
+                    data.len()
+                ))
+            } else {
+                Ok(Readout { data, size, format })
+            };
+            break;
+        }
+    }
+    app.world_mut().despawn(readback);
+    result
+}
@@ -0,0 +1,179 @@
+//! GPU check of the normal decoding, for both `NormalEncoding`s.
+//!
+//! Renders a unit sphere with `DebugView::Normals` (the decoded world-space
+//! prepass normal as RGB) through an orthographic camera looking down -Z, reads
+//! the image back and compares each pixel inside the sphere with the analytic
+//! normal at that point. `Xyz` is checked on a `StandardMaterial`, whose prepass
+//! writes `n * 0.5 + 0.5`; `Octahedral` on a material whose prepass fragment
+//! shader writes octahedral-encoded normals. A decoder that doesn't match the
+//! prepass shows up as normals that are rotated or point the wrong way. See
+//! `common` for the harness.
+
+mod common;
+
+use std::{process::ExitCode, sync::OnceLock};
+
+use bevy::{
+    camera::{RenderTarget, ScalingMode},
+    core_pipeline::tonemapping::{DebandDither, Tonemapping},
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    render::render_resource::{AsBindGroup, TextureFormat},
+    shader::{Shader, ShaderRef},
+};
+use bevy_edge_detection_outline::{DebugView, EdgeDetection, NormalEncoding};
+use common::Readout;
+
+/// Size of the check images.
+const SIZE: UVec2 = UVec2::new(256, 256);
+/// World units covered by the image along each axis; the sphere has radius 1.
+const VIEW_SIZE: f32 = 2.5;
+/// Only pixels within this radius are compared: near the silhouette the mesh
+/// tessellation and the filtered reads blend in the background.
+const CHECK_RADIUS: f32 = 0.85;
+/// Minimum cosine between decoded and expected normals (about 8 degrees, enough
+/// for the 8-bit readback and the interpolated mesh normals).
+const MIN_COSINE: f32 = 0.99;
+
+fn main() -> ExitCode {
+    common::run(&[("xyz", xyz), ("octahedral", octahedral)])
+}
+
+/// Prepass fragment shader of [`OctahedralNormals`], added to the app's shader
+/// assets before the material is used.
+static OCTAHEDRAL_PREPASS_SHADER: OnceLock<Handle<Shader>> = OnceLock::new();
+
+/// `StandardMaterial` extension whose prepass writes octahedral-encoded normals.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone, Default)]
+struct OctahedralNormals {}
+
+impl MaterialExtension for OctahedralNormals {
+    fn prepass_fragment_shader() -> ShaderRef {
+        OCTAHEDRAL_PREPASS_SHADER
+            .get()
+            .cloned()
+            .map_or(ShaderRef::Default, ShaderRef::Handle)
+    }
+}
+
+fn xyz() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(());
+    };
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial {
+            unlit: true,
+            ..default()
+        });
+    check_sphere(app, MeshMaterial3d(material), NormalEncoding::Xyz)
+}
+
+fn octahedral() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(MaterialPlugin::<
+        ExtendedMaterial<StandardMaterial, OctahedralNormals>,
+    >::default()) else {
+        return Ok(());
+    };
+    let shader = app
+        .world_mut()
+        .resource_mut::<Assets<Shader>>()
+        .add(Shader::from_wgsl(
+            include_str!("octahedral_normal_prepass.wgsl"),
+            "tests/octahedral_normal_prepass.wgsl",
+        ));
+    OCTAHEDRAL_PREPASS_SHADER
+        .set(shader)
+        .map_err(|_| "octahedral prepass shader already set")?;
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<ExtendedMaterial<StandardMaterial, OctahedralNormals>>>()
+        .add(ExtendedMaterial {
+            base: StandardMaterial {
+                unlit: true,
+                ..default()
+            },
+            extension: OctahedralNormals::default(),
+        });
+    check_sphere(app, MeshMaterial3d(material), NormalEncoding::Octahedral)
+}
+
+/// Renders the unit sphere with `material` and checks the normals decoded with
+/// `normal_encoding`.
+fn check_sphere(
+    mut app: App,
+    material: impl Bundle,
+    normal_encoding: NormalEncoding,
+) -> Result<(), String> {
+    // Linear format: the readback holds the shader output unencoded.
+    let target = common::render_target(&mut app, SIZE, TextureFormat::Rgba8Unorm);
+    let sphere = app
+        .world_mut()
+        .resource_mut::<Assets<Mesh>>()
+        .add(Sphere::new(1.0).mesh().uv(128, 64));
+    app.world_mut().spawn((Mesh3d(sphere), material));
+    // World axes match the image axes: +X right, +Y up, +Z toward the camera.
+    app.world_mut().spawn((
+        Camera3d::default(),
+        RenderTarget::Image(target.clone().into()),
+        Projection::from(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: VIEW_SIZE,
+                height: VIEW_SIZE,
+            },
+            ..OrthographicProjection::default_3d()
+        }),
+        Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        Msaa::Off,
+        Tonemapping::None,
+        DebandDither::Disabled,
+        EdgeDetection {
+            normal_encoding,
+            debug_view: DebugView::Normals,
+            ..default()
+        },
+    ));
+    let image = common::read_back(&mut app, &target)?;
+    check_normals(&image)
+}
+
+/// Decoded normal of the debug view at pixel (`x`, `y`).
+fn decoded_normal(image: &Readout, x: u32, y: u32) -> Vec3 {
+    let [r, g, b, _] = image.rgba8(x, y);
+    Vec3::new(r as f32, g as f32, b as f32) / 255.0 * 2.0 - Vec3::ONE
+}
+
+/// Normal of the unit sphere seen at pixel (`x`, `y`), if the pixel is within
+/// `CHECK_RADIUS` of its center.
+fn expected_normal(x: u32, y: u32) -> Option<Vec3> {
+    let px = ((x as f32 + 0.5) / SIZE.x as f32 - 0.5) * VIEW_SIZE;
+    let py = (0.5 - (y as f32 + 0.5) / SIZE.y as f32) * VIEW_SIZE;
+    let r2 = px * px + py * py;
+    (r2 < CHECK_RADIUS * CHECK_RADIUS).then(|| Vec3::new(px, py, (1.0 - r2).sqrt()))
+}
+
+fn check_normals(image: &Readout) -> Result<(), String> {
+    let mut worst: Option<(f32, u32, u32, Vec3, Vec3)> = None;
+    for y in 0..SIZE.y {
+        for x in 0..SIZE.x {
+            let Some(expected) = expected_normal(x, y) else {
+                continue;
+            };
+            let decoded = decoded_normal(image, x, y);
+            let cosine = decoded.normalize_or_zero().dot(expected);
+            if worst.is_none_or(|(worst, ..)| cosine < worst) {
+                worst = Some((cosine, x, y, decoded, expected));
+            }
+        }
+    }
+    let Some((cosine, x, y, decoded, expected)) = worst else {
+        return Err("no pixel inside the sphere".into());
+    };
+    if cosine < MIN_COSINE {
+        return Err(format!(
+            "normal at ({x}, {y}) is {decoded:.3}, expected {expected:.3} (cosine {cosine:.3})"
+        ));
+    }
+    Ok(())
+}
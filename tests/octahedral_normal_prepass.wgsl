@@ -0,0 +1,25 @@
+// Prepass fragment shader of the `normal_check` octahedral material: writes the
+// world normal octahedral-encoded into RG of the normal prepass, like a deferred
+// G-buffer, instead of Bevy's `n * 0.5 + 0.5` in RGB.
+
+#import bevy_pbr::prepass_io::{VertexOutput, FragmentOutput}
+
+/// Unit vector to octahedral coordinates in [0, 1].
+fn octahedral_encode(v: vec3f) -> vec2f {
+    let n = v / (abs(v.x) + abs(v.y) + abs(v.z));
+    let wrapped = (1.0 - abs(n.yx)) * select(vec2f(-1.0), vec2f(1.0), n.xy >= vec2f(0.0));
+    let xy = select(wrapped, n.xy, n.z >= 0.0);
+    return xy * 0.5 + 0.5;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> FragmentOutput {
+    var out: FragmentOutput;
+#ifdef NORMAL_PREPASS
+    out.normal = vec4f(octahedral_encode(normalize(in.world_normal)), 0.0, 1.0);
+#endif
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.frag_depth = in.unclipped_depth;
+#endif
+    return out;
+}
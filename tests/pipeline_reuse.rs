@@ -0,0 +1,90 @@
+//! GPU check that removing and re-inserting `EdgeDetection` reuses the cached
+//! pipeline instead of specializing (and compiling) a new one. See `common` for
+//! the harness.
+
+mod common;
+
+use std::process::ExitCode;
+
+use bevy::{
+    prelude::*,
+    render::{
+        RenderApp,
+        render_resource::{PipelineCache, TextureFormat},
+    },
+};
+use bevy_edge_detection_outline::{
+    EdgeDetection, EdgeDetectionPipelineId, EdgeDetectionPipelineVariants,
+};
+
+const SIZE: UVec2 = UVec2::new(64, 64);
+
+fn main() -> ExitCode {
+    common::run(&[("reinsert", reinsert)])
+}
+
+/// The render-world pipeline id of the only edge-detection view, if it has one.
+fn view_pipeline(app: &mut App) -> Option<EdgeDetectionPipelineId> {
+    let world = app.sub_app_mut(RenderApp).world_mut();
+    world
+        .query::<&EdgeDetectionPipelineId>()
+        .iter(world)
+        .next()
+        .copied()
+}
+
+/// Number of specialized keys; `None` in release builds, which don't track them.
+fn variant_count(app: &App) -> Option<usize> {
+    app.sub_app(RenderApp)
+        .world()
+        .get_resource::<EdgeDetectionPipelineVariants>()
+        .map(EdgeDetectionPipelineVariants::count)
+}
+
+/// Renders with edge detection, removes the component, re-inserts the same
+/// settings and checks that the view gets the same, already compiled pipeline
+/// back the next frame and that no new variant was specialized.
+fn reinsert() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(());
+    };
+    common::spawn_cube_on_plane(&mut app);
+    let target = common::render_target(&mut app, SIZE, TextureFormat::Rgba8UnormSrgb);
+    let settings = EdgeDetection::default();
+    let camera = app
+        .world_mut()
+        .spawn((common::cube_camera(target.clone()), settings))
+        .id();
+    // Waits until the pipelines are compiled.
+    common::read_back(&mut app, &target)?;
+    let before = view_pipeline(&mut app).ok_or("no pipeline id on the edge-detection view")?;
+    let variants_before = variant_count(&app);
+
+    app.world_mut().entity_mut(camera).remove::<EdgeDetection>();
+    app.update();
+    if view_pipeline(&mut app).is_some() {
+        return Err("pipeline id left on the view after removing EdgeDetection".into());
+    }
+
+    app.world_mut().entity_mut(camera).insert(settings);
+    app.update();
+    let after = view_pipeline(&mut app).ok_or("no pipeline id after re-inserting")?;
+    if after.id() != before.id() {
+        return Err(format!(
+            "re-inserting the same settings specialized {:?}, expected {:?}",
+            after.id(),
+            before.id()
+        ));
+    }
+    let pipeline_cache = app.sub_app(RenderApp).world().resource::<PipelineCache>();
+    if !after.is_ready(pipeline_cache) {
+        return Err("re-used pipeline is not ready".into());
+    }
+    let variants_after = variant_count(&app);
+    if variants_after != variants_before {
+        return Err(format!(
+            "pipeline variants went from {variants_before:?} to {variants_after:?}"
+        ));
+    }
+    Ok(())
+}
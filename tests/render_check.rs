@@ -0,0 +1,370 @@
+//! GPU checks of the rendered edge output, run by `cargo test` on machines with
+//! a GPU (or a software adapter): they catch shader and bind group regressions
+//! that otherwise only show up when looking at the screen. See `common` for the
+//! harness.
+
+mod common;
+
+use std::process::ExitCode;
+
+use bevy::{
+    camera::{CameraProjection, SubCameraView, visibility::RenderLayers},
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
+    math::Vec3A,
+    prelude::*,
+    render::{render_resource::TextureFormat, view::Hdr},
+};
+use bevy_edge_detection_outline::{EdgeDetection, EdgeDetectionDepthMerge, EdgeDetectionPlugin};
+use common::Readout;
+
+/// Size of the check images.
+const SIZE: UVec2 = UVec2::new(256, 256);
+/// Maximum distance, in pixels, between the cube's first/last pixel and an edge.
+const EDGE_SEARCH: u32 = 3;
+
+fn main() -> ExitCode {
+    common::run(&[
+        ("silhouette", silhouette),
+        ("custom_projection", custom_projection),
+        ("pass_through", || pass_through(1.0, false)),
+        ("pass_through_reduced_resolution", || {
+            pass_through(0.5, false)
+        }),
+        ("pass_through_force_ldr", || pass_through(1.0, true)),
+        ("pass_through_force_ldr_reduced_resolution", || {
+            pass_through(0.5, true)
+        }),
+        ("edge_color_sdr_hdr", edge_color_sdr_hdr),
+        ("depth_merge", depth_merge),
+    ])
+}
+
+fn is_edge([r, g, b, _]: [u8; 4]) -> bool {
+    r > 200 && g < 60 && b < 60
+}
+
+fn is_cube([r, _, b, _]: [u8; 4]) -> bool {
+    b > 150 && r < 100
+}
+
+/// Renders the cube with red outlines and checks that the outline is where the
+/// silhouette is: along the middle row the cube's pixels must be bordered by edge
+/// pixels on both sides, and the sky corners must stay free of edges.
+fn silhouette() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(());
+    };
+    common::spawn_cube_on_plane(&mut app);
+    let target = common::render_target(&mut app, SIZE, TextureFormat::Rgba8UnormSrgb);
+    app.world_mut().spawn((
+        common::cube_camera(target.clone()),
+        EdgeDetection {
+            edge_color: Color::srgb(1.0, 0.0, 0.0),
+            enable_color: false,
+            uv_distortion_strength: Vec2::ZERO,
+            ..default()
+        },
+    ));
+    let image = common::read_back(&mut app, &target)?;
+    check_silhouette(&image)
+}
+
+/// A perspective projection behind `Projection::Custom`, so the pass has to take
+/// the generic inverse-projection path for it.
+#[derive(Clone, Debug, Default)]
+struct WrappedPerspective(PerspectiveProjection);
+
+impl CameraProjection for WrappedPerspective {
+    fn get_clip_from_view(&self) -> Mat4 {
+        self.0.get_clip_from_view()
+    }
+
+    fn get_clip_from_view_for_sub(&self, sub_view: &SubCameraView) -> Mat4 {
+        self.0.get_clip_from_view_for_sub(sub_view)
+    }
+
+    fn update(&mut self, width: f32, height: f32) {
+        self.0.update(width, height);
+    }
+
+    fn far(&self) -> f32 {
+        self.0.far()
+    }
+
+    fn get_frustum_corners(&self, z_near: f32, z_far: f32) -> [Vec3A; 8] {
+        self.0.get_frustum_corners(z_near, z_far)
+    }
+}
+
+/// Depth edges alone under a custom projection: the silhouette must still be
+/// outlined.
+fn custom_projection() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(());
+    };
+    common::spawn_cube_on_plane(&mut app);
+    let target = common::render_target(&mut app, SIZE, TextureFormat::Rgba8UnormSrgb);
+    app.world_mut().spawn((
+        common::cube_camera(target.clone()),
+        Projection::custom(WrappedPerspective::default()),
+        EdgeDetection {
+            edge_color: Color::srgb(1.0, 0.0, 0.0),
+            enable_normal: false,
+            enable_color: false,
+            uv_distortion_strength: Vec2::ZERO,
+            ..default()
+        },
+    ));
+    let image = common::read_back(&mut app, &target)?;
+    check_silhouette(&image)
+}
+
+/// Renders the cube twice from the same viewpoint, with red edges and without
+/// edge detection, at `resolution_scale` (below 1 the edges go through the
+/// composite pass). Every pixel must either equal the reference or be pulled
+/// toward the edge color: no-edge pixels pass through bit-exact.
+///
+/// With `force_ldr` both cameras are HDR over an over-bright clear color, and
+/// no-edge pixels must equal the reference clamped to [0, 1] instead.
+fn pass_through(resolution_scale: f32, force_ldr: bool) -> Result<(), String> {
+    let plugin = EdgeDetectionPlugin {
+        force_ldr,
+        ..default()
+    };
+    let Some(mut app) = common::headless_app_with(plugin, ()) else {
+        return Ok(());
+    };
+    common::spawn_cube_on_plane(&mut app);
+    let format = if force_ldr {
+        app.insert_resource(ClearColor(Color::linear_rgb(4.0, 4.0, 4.0)));
+        TextureFormat::Rgba16Float
+    } else {
+        TextureFormat::Rgba8UnormSrgb
+    };
+    let edges = common::render_target(&mut app, SIZE, format);
+    let reference = common::render_target(&mut app, SIZE, format);
+    let edge_camera = app
+        .world_mut()
+        .spawn((
+            common::cube_camera(edges.clone()),
+            EdgeDetection {
+                edge_color: Color::srgb(1.0, 0.0, 0.0),
+                uv_distortion_strength: Vec2::ZERO,
+                resolution_scale,
+                ..default()
+            },
+        ))
+        .id();
+    // Same prepasses as the edge camera, so both main passes run alike.
+    let reference_camera = app
+        .world_mut()
+        .spawn((
+            common::cube_camera(reference.clone()),
+            DepthPrepass,
+            NormalPrepass,
+        ))
+        .id();
+    if force_ldr {
+        for camera in [edge_camera, reference_camera] {
+            app.world_mut().entity_mut(camera).insert(Hdr);
+        }
+    }
+    let edges = common::read_back(&mut app, &edges)?;
+    let reference = common::read_back(&mut app, &reference)?;
+
+    let mut unchanged = 0;
+    let mut clamped = 0;
+    for y in 0..SIZE.y {
+        for x in 0..SIZE.x {
+            let (out, src) = (edges.linear(x, y), reference.linear(x, y));
+            let expected = if force_ldr {
+                let clamp = |c: f32| c.clamp(0.0, 1.0);
+                LinearRgba::new(
+                    clamp(src.red),
+                    clamp(src.green),
+                    clamp(src.blue),
+                    clamp(src.alpha),
+                )
+            } else {
+                src
+            };
+            if out == expected {
+                unchanged += 1;
+                clamped += usize::from(expected != src);
+            } else if out.red < expected.red
+                || out.green > expected.green
+                || out.blue > expected.blue
+            {
+                return Err(format!(
+                    "pixel ({x}, {y}) is {out:?}, expected {expected:?}: changed but not toward the edge color"
+                ));
+            }
+        }
+    }
+    // Most of the image is away from the outlines.
+    if unchanged < SIZE.x * SIZE.y / 2 {
+        return Err(format!(
+            "only {unchanged} of {} pixels passed through unchanged",
+            SIZE.x * SIZE.y
+        ));
+    }
+    if force_ldr && clamped == 0 {
+        return Err("no over-bright pixel to clamp".into());
+    }
+    Ok(())
+}
+
+/// Edge color mid-tones: 0 and 1 map to themselves under the sRGB curve, so a
+/// missing or doubled conversion only shows up in between.
+const MID_TONE_EDGE: Color = Color::srgb(0.5, 0.25, 0.0);
+/// Per-channel tolerance for decoded edge colors (8-bit sRGB steps near 0.5 are
+/// about 0.004 in linear).
+const EDGE_COLOR_TOLERANCE: f32 = 0.01;
+
+/// Renders mid-tone edges over a black `flat_background` into an 8-bit sRGB
+/// target from an SDR camera and into a half-float target from an HDR one: the
+/// fully covered edge pixels must decode to the same linear color, the edge
+/// color's.
+fn edge_color_sdr_hdr() -> Result<(), String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(());
+    };
+    common::spawn_cube_on_plane(&mut app);
+    let sdr = common::render_target(&mut app, SIZE, TextureFormat::Rgba8UnormSrgb);
+    let hdr = common::render_target(&mut app, SIZE, TextureFormat::Rgba16Float);
+    let settings = EdgeDetection {
+        edge_color: MID_TONE_EDGE,
+        flat_background: Some(Color::BLACK),
+        uv_distortion_strength: Vec2::ZERO,
+        ..default()
+    };
+    app.world_mut()
+        .spawn((common::cube_camera(sdr.clone()), settings));
+    app.world_mut()
+        .spawn((common::cube_camera(hdr.clone()), Hdr, settings));
+    let sdr = strongest_edge(&common::read_back(&mut app, &sdr)?);
+    let hdr = strongest_edge(&common::read_back(&mut app, &hdr)?);
+
+    let expected = LinearRgba::from(MID_TONE_EDGE);
+    for (name, color) in [("SDR", sdr), ("HDR", hdr)] {
+        if !colors_match(color, expected) {
+            return Err(format!(
+                "{name} edge color is {color:?}, expected {expected:?}"
+            ));
+        }
+    }
+    if !colors_match(sdr, hdr) {
+        return Err(format!("SDR edge color {sdr:?} differs from HDR {hdr:?}"));
+    }
+    Ok(())
+}
+
+/// Renders the plane with a first camera and the cube over it with a second,
+/// outlined camera, with depth edges only. The horizon (the far side of the plane
+/// against the sky) is only in the first camera's depth: it must be outlined with
+/// an [`EdgeDetectionDepthMerge`] listing that camera, and not without.
+fn depth_merge() -> Result<(), String> {
+    let Some(merged) = render_layers(true)? else {
+        return Ok(());
+    };
+    let Some(unmerged) = render_layers(false)? else {
+        return Ok(());
+    };
+    check_silhouette(&merged)?;
+
+    // Left of the cube: only plane and sky.
+    let x = 8;
+    let horizon = |image: &Readout| (0..image.size.y).any(|y| is_edge(image.rgba8(x, y)));
+    if !horizon(&merged) {
+        return Err(format!("no horizon edge in column {x} with merged depth"));
+    }
+    if horizon(&unmerged) {
+        return Err(format!("horizon edge in column {x} without merged depth"));
+    }
+    Ok(())
+}
+
+fn render_layers(merge: bool) -> Result<Option<Readout>, String> {
+    let Some(mut app) = common::headless_app(()) else {
+        return Ok(None);
+    };
+    let (_, plane) = common::spawn_cube_on_plane(&mut app);
+    let target = common::render_target(&mut app, SIZE, TextureFormat::Rgba8UnormSrgb);
+    let world = app.world_mut();
+    world.entity_mut(plane).insert(RenderLayers::layer(1));
+    let plane_camera = world
+        .spawn((
+            common::cube_camera(target.clone()),
+            RenderLayers::layer(1),
+            DepthPrepass,
+        ))
+        .id();
+    let mut edge_camera = world.spawn((
+        common::cube_camera(target.clone()),
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        EdgeDetection {
+            edge_color: Color::srgb(1.0, 0.0, 0.0),
+            enable_normal: false,
+            enable_color: false,
+            uv_distortion_strength: Vec2::ZERO,
+            ..default()
+        },
+    ));
+    if merge {
+        edge_camera.insert(EdgeDetectionDepthMerge(vec![plane_camera]));
+    }
+    common::read_back(&mut app, &target).map(Some)
+}
+
+/// Linear color of the reddest pixel: over black, a fully covered edge pixel.
+fn strongest_edge(image: &Readout) -> LinearRgba {
+    (0..image.size.y)
+        .flat_map(|y| (0..image.size.x).map(move |x| image.linear(x, y)))
+        .max_by(|a, b| a.red.total_cmp(&b.red))
+        .unwrap_or(LinearRgba::NONE)
+}
+
+fn colors_match(a: LinearRgba, b: LinearRgba) -> bool {
+    [a.red - b.red, a.green - b.green, a.blue - b.blue]
+        .iter()
+        .all(|d| d.abs() <= EDGE_COLOR_TOLERANCE)
+}
+
+fn check_silhouette(image: &Readout) -> Result<(), String> {
+    let (width, height) = (image.size.x, image.size.y);
+    for (x, y) in [(0, 0), (width - 1, 0)] {
+        if is_edge(image.rgba8(x, y)) {
+            return Err(format!("edge in the empty sky at ({x}, {y})"));
+        }
+    }
+
+    let y = height / 2;
+    let center = image.rgba8(width / 2, y);
+    if !is_cube(center) {
+        return Err(format!(
+            "expected the cube at the image center, found {center:?}"
+        ));
+    }
+    let cube: Vec<u32> = (0..width).filter(|&x| is_cube(image.rgba8(x, y))).collect();
+    let (first, last) = (cube[0], cube[cube.len() - 1]);
+    let edge_near = |range: std::ops::RangeInclusive<u32>| {
+        range
+            .filter(|&x| x < width)
+            .any(|x| is_edge(image.rgba8(x, y)))
+    };
+    if !edge_near(first.saturating_sub(EDGE_SEARCH)..=first) {
+        return Err(format!(
+            "no edge left of the cube (first cube pixel at x = {first})"
+        ));
+    }
+    if !edge_near(last..=last + EDGE_SEARCH) {
+        return Err(format!(
+            "no edge right of the cube (last cube pixel at x = {last})"
+        ));
+    }
+    Ok(())
+}